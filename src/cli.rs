@@ -0,0 +1,287 @@
+use super::{export, import, TemplateOption};
+use resume::{AccessibleTheme, Resume, ThemeOption};
+use std::{
+    fs::write,
+    io::{stdout, Write},
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+const USAGE: &str = "\
+Usage:
+    resume-gen                          Open the resume generator window
+    resume-gen generate <RESUME> [--template <TEMPLATE>] [--theme <THEME>]
+                        [--output <FILE>] [--offline] [--structured-data]
+                        [--fix-contrast]
+                                        Compile a .json or .toml resume document
+    resume-gen import <FILE> [--output <FILE>]
+                                        Convert a jsonresume.org resume.json, or a
+                                        Europass CV in an .xml file, to a resume
+                                        document (default: a .toml beside it)
+    resume-gen export <RESUME> [--output <FILE>]
+                                        Convert a resume document to a Europass
+                                        CV for .xml files, or else to the
+                                        jsonresume.org schema (default: stdout)
+    resume-gen templates                List the available templates
+    resume-gen themes                   List the available themes
+    resume-gen help                     Show this message
+
+Options:
+    -l, --template <TEMPLATE>
+                            The layout to compile with (default: Classic)
+    -t, --theme <THEME>     The theme to compile with (default: Default)
+    -o, --output <FILE>     Where to write the resume, or `-` for stdout. A .pdf
+                            file gets a PDF, a .docx file gets a Word document,
+                            a .odt file gets an OpenDocument text, a .tex file
+                            gets LaTeX (moderncv), a .md file gets Markdown, a
+                            .txt file gets plain text, and anything else gets
+                            HTML
+                            (default: the resume document with an .html extension)
+        --offline           Inline all styles so the page renders without a network
+        --structured-data   Describe the person in a schema.org JSON-LD block so
+                            search engines can read it from the page
+        --fix-contrast      Adjust text colors of the theme that are too faint
+                            against its background to meet WCAG AA";
+
+// Exit codes used by the command line interface
+const EXIT_FAILURE: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+
+enum Command {
+    Generate {
+        input: PathBuf,
+        template: TemplateOption,
+        theme: ThemeOption,
+        output: Option<PathBuf>,
+        offline: bool,
+        structured_data: bool,
+        fix_contrast: bool,
+    },
+    // Convert from the JSON Resume schema or a Europass CV to a resume document
+    Import {
+        input: PathBuf,
+        output: Option<PathBuf>,
+    },
+    // Convert a resume document to the JSON Resume schema or a Europass CV
+    Export {
+        input: PathBuf,
+        output: Option<PathBuf>,
+    },
+    Templates,
+    Themes,
+    Help,
+}
+
+/// Run the command line interface if any arguments were given.
+/// Returns normally only when the graphical interface should be opened.
+pub fn run(args: Vec<String>) {
+    if args.is_empty() {
+        return;
+    }
+
+    let command = match parse(&args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            exit(EXIT_USAGE)
+        }
+    };
+
+    if let Err(e) = execute(command) {
+        eprintln!("error: {}", e);
+        exit(EXIT_FAILURE)
+    }
+    exit(0)
+}
+
+fn parse(args: &[String]) -> Result<Command, String> {
+    let (command, rest) = args.split_first().unwrap();
+    match command.as_str() {
+        "generate" => {
+            let mut input = None;
+            let mut template = TemplateOption::Classic;
+            let mut theme = ThemeOption::default();
+            let mut output = None;
+            let mut offline = false;
+            let mut structured_data = false;
+            let mut fix_contrast = false;
+
+            let mut rest = rest.iter();
+            while let Some(arg) = rest.next() {
+                match arg.as_str() {
+                    "-l" | "--template" => {
+                        let name = rest
+                            .next()
+                            .ok_or_else(|| format!("missing value for `{}`", arg))?;
+                        template = TemplateOption::from_name(name)
+                            .ok_or_else(|| format!("unknown template `{}`", name))?;
+                    }
+                    "-t" | "--theme" => {
+                        let name = rest
+                            .next()
+                            .ok_or_else(|| format!("missing value for `{}`", arg))?;
+                        theme = ThemeOption::from_name(name)
+                            .ok_or_else(|| format!("unknown theme `{}`", name))?;
+                    }
+                    "-o" | "--output" => {
+                        output = Some(PathBuf::from(
+                            rest.next()
+                                .ok_or_else(|| format!("missing value for `{}`", arg))?,
+                        ));
+                    }
+                    "--offline" => offline = true,
+                    "--structured-data" => structured_data = true,
+                    "--fix-contrast" => fix_contrast = true,
+                    flag if flag.starts_with('-') && flag != "-" => {
+                        return Err(format!("unknown option `{}`", flag))
+                    }
+                    path if input.is_none() => input = Some(PathBuf::from(path)),
+                    extra => return Err(format!("unexpected argument `{}`", extra)),
+                }
+            }
+
+            Ok(Command::Generate {
+                input: input.ok_or("missing resume document")?,
+                template,
+                theme,
+                output,
+                offline,
+                structured_data,
+                fix_contrast,
+            })
+        }
+        "import" => {
+            let (input, output) = parse_conversion(rest)?;
+            Ok(Command::Import { input, output })
+        }
+        "export" => {
+            let (input, output) = parse_conversion(rest)?;
+            Ok(Command::Export { input, output })
+        }
+        "templates" if rest.is_empty() => Ok(Command::Templates),
+        "themes" if rest.is_empty() => Ok(Command::Themes),
+        "help" | "-h" | "--help" => Ok(Command::Help),
+        other => Err(format!("unknown command `{}`", other)),
+    }
+}
+
+// The arguments of `import` and `export`: an input file and maybe an output file
+fn parse_conversion(args: &[String]) -> Result<(PathBuf, Option<PathBuf>), String> {
+    let mut input = None;
+    let mut output = None;
+
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "-o" | "--output" => {
+                output = Some(PathBuf::from(
+                    rest.next()
+                        .ok_or_else(|| format!("missing value for `{}`", arg))?,
+                ));
+            }
+            flag if flag.starts_with('-') && flag != "-" => {
+                return Err(format!("unknown option `{}`", flag))
+            }
+            path if input.is_none() => input = Some(PathBuf::from(path)),
+            extra => return Err(format!("unexpected argument `{}`", extra)),
+        }
+    }
+
+    Ok((input.ok_or("missing input file")?, output))
+}
+
+// Warn about everything a conversion had to leave out
+fn report_unmapped(unmapped: &[String]) {
+    for field in unmapped {
+        eprintln!("warning: could not convert `{}`, so it was left out", field);
+    }
+}
+
+fn execute(command: Command) -> Result<(), String> {
+    match command {
+        Command::Generate {
+            input,
+            template,
+            theme,
+            output,
+            offline,
+            structured_data,
+            fix_contrast,
+        } => {
+            let resume = Resume::from_path(&input).map_err(|e| e.to_string())?;
+            let output = output.unwrap_or_else(|| input.with_extension("html"));
+            let name = String::from(theme);
+            let theme = theme.get_theme();
+            let accessible = AccessibleTheme::new(&*theme);
+            if !fix_contrast {
+                for issue in theme.contrast_issues() {
+                    eprintln!("warning: in theme {}, {}", name, issue);
+                }
+            }
+
+            let bytes = export(
+                &resume,
+                template.get_template(),
+                if fix_contrast { &accessible } else { &*theme },
+                offline,
+                structured_data,
+                &output,
+            )?;
+
+            if output == Path::new("-") {
+                stdout()
+                    .write_all(&bytes)
+                    .map_err(|e| format!("could not write to stdout: {}", e))
+            } else {
+                write(&output, bytes)
+                    .map_err(|e| format!("could not write {}: {}", output.display(), e))
+            }
+        }
+        Command::Import { input, output } => {
+            let conversion = import(&input)?;
+            report_unmapped(&conversion.unmapped);
+
+            let output = output.unwrap_or_else(|| input.with_extension("toml"));
+            conversion.value.to_path(&output).map_err(|e| e.to_string())
+        }
+        Command::Export { input, output } => {
+            let resume = Resume::from_path(&input).map_err(|e| e.to_string())?;
+            let europass = output.as_ref().is_some_and(|output| {
+                output
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("xml"))
+            });
+            let conversion = if europass {
+                resume.to_europass()
+            } else {
+                resume.to_json_resume().map_err(|e| e.to_string())?
+            };
+            report_unmapped(&conversion.unmapped);
+
+            match output {
+                Some(output) if output != Path::new("-") => write(&output, conversion.value)
+                    .map_err(|e| format!("could not write {}: {}", output.display(), e)),
+                _ => {
+                    println!("{}", conversion.value);
+                    Ok(())
+                }
+            }
+        }
+        Command::Templates => {
+            for option in TemplateOption::all().iter() {
+                println!("{}", String::from(*option));
+            }
+            Ok(())
+        }
+        Command::Themes => {
+            for option in ThemeOption::all().iter() {
+                println!("{}", String::from(*option));
+            }
+            Ok(())
+        }
+        Command::Help => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+    }
+}
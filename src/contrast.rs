@@ -0,0 +1,155 @@
+use super::{Color, Theme};
+use core::fmt::{Display, Formatter};
+
+/// The lowest contrast ratio WCAG level AA allows for body text
+pub const AA_CONTRAST_RATIO: f64 = 4.5;
+
+/// A color of a theme that is too close to its background to read easily
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct ContrastIssue {
+    pub color: Color,
+    // The contrast ratio against `DefaultBackground`, from 1 to 21
+    pub ratio: f64,
+}
+
+impl Display for ContrastIssue {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "{:?} has a contrast ratio of {:.2}:1 against the background, below the {}:1 WCAG AA requires",
+            self.color, self.ratio, AA_CONTRAST_RATIO
+        )
+    }
+}
+
+/// The WCAG contrast ratio between two colors, from 1 for identical
+/// colors to 21 for black on white
+pub fn contrast_ratio(a: (u8, u8, u8), b: (u8, u8, u8)) -> f64 {
+    let (a, b) = (luminance(a), luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+// The relative luminance of a color, as WCAG defines it
+fn luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    let channel = |c: u8| {
+        let c = f64::from(c) / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b)
+}
+
+/// Darken `color` on light backgrounds, or lighten it on dark ones, keeping
+/// its hue and saturation, until it has at least `target` contrast with
+/// `background`. Black or white is returned if even they fall short.
+pub fn adjust_lightness(
+    color: (u8, u8, u8),
+    background: (u8, u8, u8),
+    target: f64,
+) -> (u8, u8, u8) {
+    if contrast_ratio(color, background) >= target {
+        return color;
+    }
+
+    let (hue, saturation, lightness) = to_hsl(color);
+    let darken =
+        contrast_ratio(background, (0, 0, 0)) > contrast_ratio(background, (255, 255, 255));
+    // Contrast grows steadily as the lightness moves away from the
+    // background's, so search for the smallest change that is enough
+    let (mut low, mut high) = if darken {
+        (0.0, lightness)
+    } else {
+        (lightness, 1.0)
+    };
+    for _ in 0..32 {
+        let middle = (low + high) / 2.0;
+        let enough = contrast_ratio(from_hsl(hue, saturation, middle), background) >= target;
+        if enough == darken {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    from_hsl(hue, saturation, if darken { low } else { high })
+}
+
+fn to_hsl((r, g, b): (u8, u8, u8)) -> (f64, f64, f64) {
+    let (r, g, b) = (
+        f64::from(r) / 255.0,
+        f64::from(g) / 255.0,
+        f64::from(b) / 255.0,
+    );
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+    let delta = max - min;
+    if delta == 0.0 {
+        return (0.0, 0.0, lightness);
+    }
+
+    let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+    let hue = if max == r {
+        ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+    (hue * 60.0, saturation, lightness)
+}
+
+fn from_hsl(hue: f64, saturation: f64, lightness: f64) -> (u8, u8, u8) {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0).rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match (hue / 60.0) as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    let channel = |c: f64| ((c + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    (channel(r), channel(g), channel(b))
+}
+
+/// Another theme with every text color adjusted as little as possible to
+/// meet WCAG AA against its background. Everything but the colors is left
+/// to the original theme.
+pub struct AccessibleTheme<'a> {
+    theme: &'a dyn Theme,
+}
+
+impl<'a> AccessibleTheme<'a> {
+    pub fn new(theme: &'a dyn Theme) -> Self {
+        Self { theme }
+    }
+}
+
+impl Theme for AccessibleTheme<'_> {
+    fn get_color_rgb(&self, color: Color) -> (u8, u8, u8) {
+        let rgb = self.theme.get_color_rgb(color);
+        if Color::text().contains(&color) {
+            let background = self.theme.get_color_rgb(Color::DefaultBackground);
+            adjust_lightness(rgb, background, AA_CONTRAST_RATIO)
+        } else {
+            rgb
+        }
+    }
+
+    fn get_document_css(&self) -> String {
+        self.theme.get_document_css()
+    }
+
+    fn get_offline_document_css(&self) -> String {
+        self.theme.get_offline_document_css()
+    }
+
+    fn compile_section_html(&self, content: String) -> String {
+        self.theme.compile_section_html(content)
+    }
+}
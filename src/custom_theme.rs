@@ -0,0 +1,192 @@
+use super::{
+    document::parse,
+    theme::{DOCUMENT_CSS, OFFLINE_DOCUMENT_CSS},
+    Color, DocumentError, Format, Theme,
+};
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fs::{read_dir, read_to_string},
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+/// How a theme draws the box around each section
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SectionStyle {
+    // A bordered Bootstrap card, like the built-in themes
+    #[default]
+    Card,
+    // A card with well rounded corners
+    Rounded,
+    // No border at all, only the padding of a card
+    Flat,
+}
+
+/// A theme read from a JSON or TOML file, so that themes can be added
+/// without recompiling. Every `Color` must be given as a `#rrggbb` code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CustomTheme {
+    name: String,
+    colors: BTreeMap<Color, (u8, u8, u8)>,
+    font_family: Option<String>,
+    css: String,
+    section_style: SectionStyle,
+}
+
+// The layout of a theme file, before its colors are checked
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    name: String,
+    colors: BTreeMap<Color, String>,
+    #[serde(default)]
+    font_family: Option<String>,
+    // Appended to the document's styles, after the theme's fonts
+    #[serde(default)]
+    css: String,
+    #[serde(default)]
+    section_style: SectionStyle,
+}
+
+impl CustomTheme {
+    /// Read a theme file, choosing the format from the file extension
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, DocumentError> {
+        let path = path.as_ref();
+        let format =
+            Format::from_path(path).ok_or_else(|| DocumentError::UnknownFormat(path.into()))?;
+        let text = read_to_string(path).map_err(|e| DocumentError::Io(path.into(), e))?;
+        Self::from_document(&text, format)
+    }
+
+    pub fn from_document(text: &str, format: Format) -> Result<Self, DocumentError> {
+        let file: ThemeFile = parse(text, format)?;
+
+        let missing: Vec<String> = Color::all()
+            .iter()
+            .filter(|color| !file.colors.contains_key(color))
+            .map(|color| color_name(*color))
+            .collect();
+        if !missing.is_empty() {
+            return Err(DocumentError::Parse {
+                path: String::from("colors"),
+                message: format!("missing {}", missing.join(", ")),
+            });
+        }
+
+        // Both end up inside a <style> element, which markup could close early
+        let fields = [
+            ("font_family", file.font_family.as_deref()),
+            ("css", Some(&*file.css)),
+        ];
+        for &(path, value) in fields.iter() {
+            if value.is_some_and(|value| value.contains('<')) {
+                return Err(DocumentError::Parse {
+                    path: String::from(path),
+                    message: String::from(
+                        "`<` is not allowed, since it could end the theme's styles",
+                    ),
+                });
+            }
+        }
+
+        let mut colors = BTreeMap::new();
+        for (color, hex) in file.colors {
+            let rgb = parse_hex(&hex).ok_or_else(|| DocumentError::Parse {
+                path: format!("colors.{}", color_name(color)),
+                message: format!("`{}` is not a color, expected one like #1e90ff", hex),
+            })?;
+            colors.insert(color, rgb);
+        }
+
+        Ok(Self {
+            name: file.name,
+            colors,
+            font_family: file.font_family,
+            css: file.css,
+            section_style: file.section_style,
+        })
+    }
+
+    /// Read every `.json` and `.toml` theme in `dir`, in order of file name,
+    /// along with the path each was read from. A missing directory has no
+    /// themes, and a file that cannot be read gives an error without
+    /// stopping the others from loading.
+    pub fn load_dir(dir: impl AsRef<Path>) -> Vec<(PathBuf, Result<Self, DocumentError>)> {
+        let dir = dir.as_ref();
+        let entries = match read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Vec::new(),
+            Err(e) => return vec![(dir.into(), Err(DocumentError::Io(dir.into(), e)))],
+        };
+
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && Format::from_path(path).is_some())
+            .collect();
+        paths.sort();
+        paths
+            .into_iter()
+            .map(|path| {
+                let theme = Self::from_path(&path);
+                (path, theme)
+            })
+            .collect()
+    }
+
+    /// The name the theme is listed under
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn font_css(&self, fallback: &str) -> String {
+        match &self.font_family {
+            Some(font_family) => format!("\n* {{\n    font-family: {};\n}}\n", font_family),
+            None => fallback.to_string(),
+        }
+    }
+}
+
+impl Theme for CustomTheme {
+    fn get_color_rgb(&self, color: Color) -> (u8, u8, u8) {
+        self.colors[&color]
+    }
+
+    fn get_document_css(&self) -> String {
+        self.font_css(DOCUMENT_CSS) + &self.css
+    }
+
+    fn get_offline_document_css(&self) -> String {
+        self.font_css(OFFLINE_DOCUMENT_CSS) + &self.css
+    }
+
+    fn compile_section_html(&self, content: String) -> String {
+        match self.section_style {
+            SectionStyle::Card => format!("<div class=\"card\" style=\"height:100%; width:100%;\"><div class=\"card-body\" style=\"height:100%; width:100%;\">{}</div></div>", content),
+            SectionStyle::Rounded => format!("<div class=\"card\" style=\"height:100%; width:100%; border-radius: 1rem;\"><div class=\"card-body\" style=\"height:100%; width:100%;\">{}</div></div>", content),
+            SectionStyle::Flat => format!("<div style=\"height:100%; width:100%; padding: 1.25rem;\">{}</div>", content),
+        }
+    }
+}
+
+// The name of a color as it is written in theme files
+fn color_name(color: Color) -> String {
+    let mut name = String::new();
+    for ch in format!("{:?}", color).chars() {
+        if ch.is_uppercase() && !name.is_empty() {
+            name.push('_');
+        }
+        name.push(ch.to_ascii_lowercase());
+    }
+    name
+}
+
+fn parse_hex(hex: &str) -> Option<(u8, u8, u8)> {
+    let digits = hex.strip_prefix('#')?;
+    if digits.len() != 6 || !digits.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
@@ -0,0 +1,256 @@
+use core::{
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt::{Display, Formatter},
+    ops::RangeInclusive,
+    str::FromStr,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+// The years a document may hold, which keeps date arithmetic from overflowing
+pub(crate) const YEARS: RangeInclusive<u32> = 1..=9999;
+
+/// The start or end of a job or of schooling, as precise as it is known.
+/// In documents a date is written as a year (`2021`), a year and month
+/// (`"2021-03"`), or `"present"` for something that has not ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Date {
+    Year(u32),
+    // The month counts from 1 for January
+    Month(u32, u32),
+    Present,
+}
+
+impl Date {
+    /// The year this date falls in, with `Present` meaning the current year
+    pub fn year(&self) -> u32 {
+        match self {
+            Self::Year(year) | Self::Month(year, _) => *year,
+            Self::Present => today().0,
+        }
+    }
+
+    /// Has this date gone by? A year has only passed once it is over.
+    pub fn has_passed(&self) -> bool {
+        let (year, month) = today();
+        match self {
+            Self::Year(y) => *y < year,
+            Self::Month(y, m) => (*y, *m) < (year, month),
+            Self::Present => false,
+        }
+    }
+
+    /// Format a range such as `Mar 2021 – Present (1 yr 7 mos)`. The
+    /// duration is left out when the dates are not precise enough for one.
+    pub fn range(start: &Self, end: &Self) -> String {
+        match Self::duration(start, end) {
+            Some(duration) => format!("{} – {} ({})", start, end, duration),
+            None => format!("{} – {}", start, end),
+        }
+    }
+
+    /// Format a range where either end may be unknown, like `Since Mar 2021`
+    pub fn optional_range(start: Option<Self>, end: Option<Self>) -> Option<String> {
+        match (start, end) {
+            (Some(start), Some(end)) => Some(Self::range(&start, &end)),
+            (Some(start), None) => Some(format!("Since {}", start)),
+            (None, Some(end)) => Some(end.to_string()),
+            (None, None) => None,
+        }
+    }
+
+    /// How long a range lasted, such as `2 yrs 3 mos`. Both ends of a
+    /// range are included, so `Mar 2021 – Mar 2021` lasted one month.
+    /// Ranges given only in years are measured in whole years.
+    pub fn duration(start: &Self, end: &Self) -> Option<String> {
+        let months = match (start.month_index(), end.month_index()) {
+            (Some(start), Some(end)) => end.checked_sub(start)? + 1,
+            _ => end.year().checked_sub(start.year())?.checked_mul(12)?,
+        };
+
+        let plural = |n: u32, unit: &str| match n {
+            1 => format!("1 {}", unit),
+            n => format!("{} {}s", n, unit),
+        };
+        match (months / 12, months % 12) {
+            (0, 0) => None,
+            (0, months) => Some(plural(months, "mo")),
+            (years, 0) => Some(plural(years, "yr")),
+            (years, months) => Some(format!("{} {}", plural(years, "yr"), plural(months, "mo"))),
+        }
+    }
+
+    // The number of months since year 0, if the month is known and the
+    // count fits. Months outside 1 to 12 are taken as the nearest one.
+    fn month_index(&self) -> Option<u32> {
+        let (year, month) = match self {
+            Self::Year(_) => return None,
+            Self::Month(year, month) => (*year, *month),
+            Self::Present => today(),
+        };
+        year.checked_mul(12)?.checked_add(month.clamp(1, 12) - 1)
+    }
+
+    // Dates are ordered by when they start, with `Present` after every other
+    // date. The last part tells the variants apart, so that only equal dates
+    // compare as equal.
+    fn sort_key(&self) -> (u32, u32, u8) {
+        match self {
+            Self::Year(year) => (*year, 0, 0),
+            Self::Month(year, month) => (*year, *month, 1),
+            Self::Present => (u32::MAX, u32::MAX, 2),
+        }
+    }
+}
+
+impl PartialOrd for Date {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Date {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            Self::Year(year) => write!(f, "{}", year),
+            Self::Month(year, month) => {
+                write!(f, "{} {}", MONTHS[(*month).clamp(1, 12) as usize - 1], year)
+            }
+            Self::Present => write!(f, "Present"),
+        }
+    }
+}
+
+impl From<u32> for Date {
+    fn from(year: u32) -> Self {
+        Self::Year(year)
+    }
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    /// Read a date typed by a person: `2021`, `2021-03`, `03/2021`,
+    /// `Mar 2021`, `March 2021`, or `present`
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        let invalid = || {
+            format!(
+                "`{}` is not a date, expected a year like `2021`, a month like `2021-03` or `Mar 2021`, or `present`",
+                text
+            )
+        };
+        let year = |year: &str| match year.trim().parse::<u32>() {
+            Ok(year) if YEARS.contains(&year) => Ok(year),
+            _ => Err(invalid()),
+        };
+        let month = |month: &str| match month.trim().parse::<u32>() {
+            Ok(month) if (1..=12).contains(&month) => Ok(month),
+            _ => Err(invalid()),
+        };
+
+        if ["present", "now", "current", "ongoing"]
+            .iter()
+            .any(|word| text.eq_ignore_ascii_case(word))
+        {
+            Ok(Self::Present)
+        } else if let Some((y, m)) = text.split_once('-') {
+            Ok(Self::Month(year(y)?, month(m)?))
+        } else if let Some((m, y)) = text.split_once('/') {
+            Ok(Self::Month(year(y)?, month(m)?))
+        } else if let Some((m, y)) = text.split_once(char::is_whitespace) {
+            let m = m.trim_end_matches('.').to_lowercase();
+            let month = MONTHS
+                .iter()
+                .position(|name| m.get(..3).is_some_and(|m| name.eq_ignore_ascii_case(m)))
+                .ok_or_else(invalid)?;
+            Ok(Self::Month(year(y)?, month as u32 + 1))
+        } else {
+            Ok(Self::Year(year(text)?))
+        }
+    }
+}
+
+impl Serialize for Date {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Year(year) => serializer.serialize_u32(*year),
+            Self::Month(year, month) => {
+                serializer.serialize_str(&format!("{:04}-{:02}", year, month))
+            }
+            Self::Present => serializer.serialize_str("present"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Date;
+
+            fn expecting(&self, f: &mut Formatter) -> core::fmt::Result {
+                write!(f, "a year, a \"YYYY-MM\" month, or \"present\"")
+            }
+
+            fn visit_u64<E: de::Error>(self, year: u64) -> Result<Date, E> {
+                u32::try_from(year)
+                    .ok()
+                    .filter(|year| YEARS.contains(year))
+                    .map(Date::Year)
+                    .ok_or_else(|| E::custom(format!("{} is not a year", year)))
+            }
+
+            fn visit_i64<E: de::Error>(self, year: i64) -> Result<Date, E> {
+                u32::try_from(year)
+                    .ok()
+                    .filter(|year| YEARS.contains(year))
+                    .map(Date::Year)
+                    .ok_or_else(|| E::custom(format!("{} is not a year", year)))
+            }
+
+            fn visit_str<E: de::Error>(self, text: &str) -> Result<Date, E> {
+                text.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+// The current year and month in UTC
+fn today() -> (u32, u32) {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / 86_400)
+        .unwrap_or_default() as i64;
+
+    // Convert days since 1970-01-01 to a civil date, following
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as u32, month as u32)
+}
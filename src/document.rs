@@ -0,0 +1,223 @@
+use super::{Proficiency, Resume};
+use core::fmt::{Display, Formatter};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fs::{read_to_string, write},
+    path::{Path, PathBuf},
+};
+
+// The version of the on-disk resume document written by this crate. Bump
+// it whenever `Resume` gains or changes a field, so that older versions of
+// this crate reject newer documents as too new rather than as invalid. Older
+// documents must stay readable, with serde aliases or an upgrade in
+// `Resume::from_document`.
+//
+// 1: name, profession, description, skills, education and work
+// 2: contact information, and dates with months or `present`
+// 3: projects, certifications, awards, publications, work highlights,
+//    custom sections and the section layout
+pub const DOCUMENT_VERSION: u32 = 3;
+
+/// The text formats a resume document can be stored in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Format {
+    Json,
+    Toml,
+}
+
+impl Format {
+    /// Pick a format from a file's extension (`.json` or `.toml`)
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        match path
+            .as_ref()
+            .extension()?
+            .to_str()?
+            .to_lowercase()
+            .as_str()
+        {
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+}
+
+/// The outcome of converting a resume to or from another schema. Anything
+/// that has no place in the other schema is left out and listed instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Conversion<T> {
+    pub value: T,
+    // The paths of the fields that were left out, such as `work[0].location`
+    pub unmapped: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum DocumentError {
+    Io(PathBuf, std::io::Error),
+    // The file extension did not name a known format
+    UnknownFormat(PathBuf),
+    // The document was written by a newer version of this crate
+    UnsupportedVersion(u32),
+    // `path` is the dotted path to the offending field, such as `education[1].degree`
+    Parse { path: String, message: String },
+    Serialize(String),
+}
+
+impl Display for DocumentError {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            Self::Io(path, e) => write!(f, "could not access {}: {}", path.display(), e),
+            Self::UnknownFormat(path) => write!(
+                f,
+                "could not tell the format of {}, expected a .json or .toml file",
+                path.display()
+            ),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "document version {} is newer than the supported version {}",
+                version, DOCUMENT_VERSION
+            ),
+            Self::Parse { path, message } if path == "." => write!(f, "{}", message),
+            Self::Parse { path, message } => write!(f, "invalid field `{}`: {}", path, message),
+            Self::Serialize(message) => write!(f, "could not write document: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for DocumentError {}
+
+impl<E: Display> From<serde_path_to_error::Error<E>> for DocumentError {
+    fn from(e: serde_path_to_error::Error<E>) -> Self {
+        Self::Parse {
+            path: e.path().to_string(),
+            message: e.inner().to_string(),
+        }
+    }
+}
+
+// Only the header is read on the first pass, so that the version can be
+// checked before the rest of the document is interpreted.
+#[derive(Deserialize)]
+struct Header {
+    version: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct Document<'a> {
+    version: u32,
+    #[serde(flatten)]
+    resume: &'a Resume,
+}
+
+pub(crate) fn parse<T: DeserializeOwned>(text: &str, format: Format) -> Result<T, DocumentError> {
+    Ok(match format {
+        Format::Json => {
+            serde_path_to_error::deserialize(&mut serde_json::Deserializer::from_str(text))?
+        }
+        Format::Toml => serde_path_to_error::deserialize(
+            toml::Deserializer::parse(text).map_err(|e| DocumentError::Parse {
+                path: String::from("."),
+                message: e.to_string(),
+            })?,
+        )?,
+    })
+}
+
+impl Resume {
+    /// Read a resume document, choosing the format from the file extension
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, DocumentError> {
+        let path = path.as_ref();
+        let format =
+            Format::from_path(path).ok_or_else(|| DocumentError::UnknownFormat(path.into()))?;
+        let text = read_to_string(path).map_err(|e| DocumentError::Io(path.into(), e))?;
+        Self::from_document(&text, format)
+    }
+
+    /// Write this resume as a document, choosing the format from the file extension
+    pub fn to_path(&self, path: impl AsRef<Path>) -> Result<(), DocumentError> {
+        let path = path.as_ref();
+        let format =
+            Format::from_path(path).ok_or_else(|| DocumentError::UnknownFormat(path.into()))?;
+        write(path, self.to_document(format)?).map_err(|e| DocumentError::Io(path.into(), e))
+    }
+
+    pub fn from_document(text: &str, format: Format) -> Result<Self, DocumentError> {
+        let header: Header = parse(text, format)?;
+        match header.version {
+            Some(version) if version > DOCUMENT_VERSION => {
+                return Err(DocumentError::UnsupportedVersion(version))
+            }
+            Some(_) => {}
+            None => {
+                return Err(DocumentError::Parse {
+                    path: String::from("version"),
+                    message: String::from("missing document version"),
+                })
+            }
+        }
+
+        // The version belongs to the document rather than to the resume, so
+        // it is taken out before the resume rejects any field it does not know
+        Ok(match format {
+            Format::Json => {
+                let mut document: serde_json::Value = parse(text, format)?;
+                if let Some(fields) = document.as_object_mut() {
+                    fields.remove("version");
+                }
+                serde_path_to_error::deserialize(document)?
+            }
+            Format::Toml => {
+                let mut document: toml::Table = parse(text, format)?;
+                document.remove("version");
+                serde_path_to_error::deserialize(document)?
+            }
+        })
+    }
+
+    pub fn to_document(&self, format: Format) -> Result<String, DocumentError> {
+        let document = Document {
+            version: DOCUMENT_VERSION,
+            resume: self,
+        };
+        match format {
+            Format::Json => serde_json::to_string_pretty(&document)
+                .map_err(|e| DocumentError::Serialize(e.to_string())),
+            Format::Toml => toml::to_string_pretty(&document)
+                .map_err(|e| DocumentError::Serialize(e.to_string())),
+        }
+    }
+}
+
+// Skills are stored as `{ name, level }` tables rather than tuples so that
+// the TOML form stays readable and a missing level can simply be omitted.
+pub(crate) mod skills {
+    use super::Proficiency;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Skill {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        level: Option<Proficiency>,
+    }
+
+    pub fn serialize<S: Serializer>(
+        skills: &[(String, Option<Proficiency>)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(skills.iter().map(|(name, level)| Skill {
+            name: name.clone(),
+            level: *level,
+        }))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<(String, Option<Proficiency>)>, D::Error> {
+        Ok(Vec::<Skill>::deserialize(deserializer)?
+            .into_iter()
+            .map(|skill| (skill.name, skill.level))
+            .collect())
+    }
+}
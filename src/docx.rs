@@ -0,0 +1,517 @@
+use super::{
+    html::escape, pdf::strip_tags, zip::ZipWriter, Color, HorizontalAlignment, Theme, HTML,
+};
+
+// Sizes are in half-points, matching the PDF's 10, 24 and 14 point text
+const FONT_SIZE: u32 = 20;
+const TITLE_FONT_SIZE: u32 = 48;
+const SECTION_TITLE_FONT_SIZE: u32 = 28;
+
+// An A4 page with margins of 15mm, in twentieths of a point
+const PAGE_WIDTH: u32 = 11906;
+const PAGE_HEIGHT: u32 = 16838;
+const PAGE_MARGIN: u32 = 850;
+const TEXT_WIDTH: u32 = PAGE_WIDTH - 2 * PAGE_MARGIN;
+// The padding inside the frame of a section
+const SECTION_PADDING: u32 = 113;
+
+// Percent bars are drawn as a run of shaded spaces this many characters long
+const BAR_LENGTH: u32 = 20;
+
+// The relationships every document has, before those of its links
+const RELATIONSHIPS: [(&str, &str); 3] = [
+    ("styles", "styles.xml"),
+    ("numbering", "numbering.xml"),
+    ("settings", "settings.xml"),
+];
+
+const WORD_NAMESPACES: &str = "xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\" xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\"";
+
+impl HTML {
+    /// Render this tree as a Word document using the theme's colors. Grids
+    /// and sections become tables, lists are numbered by Word, and percent
+    /// bars are drawn with shading.
+    pub fn compile_docx(&self, theme: &dyn Theme) -> Vec<u8> {
+        let mut writer = DocxWriter {
+            theme,
+            links: Vec::new(),
+            lists: Vec::new(),
+            list_level: 0,
+            numbered: None,
+        };
+        let body = writer.block(self, RunStyle::default(), None, TEXT_WIDTH);
+
+        let mut zip = ZipWriter::default();
+        zip.deflate("[Content_Types].xml", CONTENT_TYPES.as_bytes());
+        zip.deflate("_rels/.rels", PACKAGE_RELATIONSHIPS.as_bytes());
+        zip.deflate(
+            "docProps/core.xml",
+            core_properties(self.first_title().unwrap_or_default()).as_bytes(),
+        );
+        zip.deflate(
+            "word/_rels/document.xml.rels",
+            writer.relationships().as_bytes(),
+        );
+        zip.deflate(
+            "word/document.xml",
+            format!(
+                "{}<w:document {}><w:background w:color=\"{}\"/><w:body>{}<w:sectPr><w:pgSz w:w=\"{}\" w:h=\"{}\"/><w:pgMar w:top=\"{m}\" w:right=\"{m}\" w:bottom=\"{m}\" w:left=\"{m}\" w:header=\"0\" w:footer=\"0\" w:gutter=\"0\"/></w:sectPr></w:body></w:document>",
+                XML_DECLARATION,
+                WORD_NAMESPACES,
+                hex(theme, Color::DefaultBackground),
+                body,
+                PAGE_WIDTH,
+                PAGE_HEIGHT,
+                m = PAGE_MARGIN
+            )
+            .as_bytes(),
+        );
+        zip.deflate("word/styles.xml", styles(theme).as_bytes());
+        zip.deflate("word/numbering.xml", writer.numbering().as_bytes());
+        zip.deflate(
+            "word/settings.xml",
+            format!(
+                "{}<w:settings {}><w:displayBackgroundShape/></w:settings>",
+                XML_DECLARATION, WORD_NAMESPACES
+            )
+            .as_bytes(),
+        );
+        zip.finish()
+    }
+}
+
+// How the text of a run looks, passed down from the elements around it
+#[derive(Clone, Copy, Default)]
+struct RunStyle {
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    color: Option<Color>,
+    shading: Option<Color>,
+}
+
+struct DocxWriter<'a> {
+    theme: &'a dyn Theme,
+    // The target of every link, in the order they appear
+    links: Vec<String>,
+    // Whether each list is ordered. Each list gets its own numbering, so
+    // that ordered lists start counting from one again.
+    lists: Vec<bool>,
+    // How deeply the current list is nested in others
+    list_level: u32,
+    // The numbering of a list item, until its first paragraph takes it
+    numbered: Option<(usize, u32)>,
+}
+
+impl DocxWriter<'_> {
+    fn block(
+        &mut self,
+        html: &HTML,
+        style: RunStyle,
+        align: Option<&'static str>,
+        width: u32,
+    ) -> String {
+        match html {
+            HTML::Document(items) | HTML::Container(items) | HTML::Column(items) => items
+                .iter()
+                .map(|item| self.block(item, style, align, width))
+                .collect(),
+
+            HTML::Row(items) if items.len() == 1 => self.block(&items[0], style, align, width),
+            HTML::Row(items) => {
+                let columns = items.iter().map(|item| (1, item)).collect();
+                self.table(columns, style, align, width, None)
+            }
+            HTML::Grid(columns) => {
+                let columns = columns
+                    .iter()
+                    .map(|(span, item)| (u32::from(*span), item))
+                    .collect();
+                self.table(columns, style, align, width, None)
+            }
+            HTML::Section(contents) => {
+                let border = hex(self.theme, Color::Grey);
+                self.table(vec![(1, contents)], style, align, width, Some(&border))
+            }
+
+            HTML::Aligned(contents, hori, _) => self.block(
+                contents,
+                style,
+                match hori {
+                    HorizontalAlignment::Left => Some("left"),
+                    HorizontalAlignment::Right => Some("right"),
+                    HorizontalAlignment::Center => Some("center"),
+                    HorizontalAlignment::SameAsParent => align,
+                },
+                width,
+            ),
+
+            HTML::Title(_) => {
+                let runs = self.runs(html, style);
+                self.paragraph(Some("Title"), align, &runs)
+            }
+            HTML::SectionTitle(_) => {
+                let runs = self.runs(html, style);
+                self.paragraph(Some("Heading1"), align, &runs)
+            }
+
+            HTML::Text(_)
+            | HTML::Span(_)
+            | HTML::Paragraph(_)
+            | HTML::Raw(_)
+            | HTML::Badge(_)
+            | HTML::Link(_, _)
+            | HTML::PercentBar(_, _) => {
+                let runs = self.runs(html, style);
+                self.paragraph(None, align, &runs)
+            }
+
+            HTML::OrderedList(items) | HTML::UnorderedList(items) => {
+                self.lists.push(matches!(html, HTML::OrderedList(_)));
+                let list = self.lists.len();
+                self.list_level += 1;
+                let mut xml = String::new();
+                for item in items {
+                    self.numbered = Some((list, self.list_level - 1));
+                    xml.push_str(&self.block(item, style, None, width));
+                }
+                self.numbered = None;
+                self.list_level -= 1;
+                xml
+            }
+
+            HTML::Italics(contents) => self.block(
+                contents,
+                RunStyle {
+                    italic: true,
+                    ..style
+                },
+                align,
+                width,
+            ),
+            HTML::Bold(contents) => self.block(
+                contents,
+                RunStyle {
+                    bold: true,
+                    ..style
+                },
+                align,
+                width,
+            ),
+            HTML::ColoredForeground(contents, color) => self.block(
+                contents,
+                RunStyle {
+                    color: Some(*color),
+                    ..style
+                },
+                align,
+                width,
+            ),
+            HTML::ColoredBackground(contents, color) => self.block(
+                contents,
+                RunStyle {
+                    shading: Some(*color),
+                    ..style
+                },
+                align,
+                width,
+            ),
+            HTML::Rectangle(contents, _, _) | HTML::Microformat(contents, _) => {
+                self.block(contents, style, align, width)
+            }
+
+            HTML::JsonLd(_) | HTML::FadeIn(_) => String::new(),
+        }
+    }
+
+    fn runs(&mut self, html: &HTML, style: RunStyle) -> String {
+        match html {
+            HTML::Text(text) | HTML::Span(text) | HTML::Title(text) | HTML::SectionTitle(text) => {
+                self.run(text, style)
+            }
+            HTML::Raw(markup) => self.run(&strip_tags(markup), style),
+            // Colored like the badges of the webpage
+            HTML::Badge(label) => self.run(
+                &format!("\u{a0}{}\u{a0}", label),
+                RunStyle {
+                    bold: true,
+                    color: Some(Color::DefaultBackground),
+                    shading: Some(Color::DefaultSubtitle),
+                    ..style
+                },
+            ),
+            HTML::PercentBar(part, label) => {
+                let filled = (part.min(&100) * BAR_LENGTH + 50) / 100;
+                let bar = |length: u32| "\u{a0}".repeat(length as usize);
+                self.run(
+                    &bar(filled),
+                    RunStyle {
+                        shading: Some(Color::DefaultSectionTitle),
+                        ..style
+                    },
+                ) + &self.run(
+                    &bar(BAR_LENGTH - filled),
+                    RunStyle {
+                        shading: Some(Color::Grey),
+                        ..style
+                    },
+                ) + &self.run(&format!(" {}", label), style)
+            }
+            HTML::Link(contents, url) => {
+                self.links.push(url.clone());
+                let id = RELATIONSHIPS.len() + self.links.len();
+                format!(
+                    "<w:hyperlink r:id=\"rId{}\">{}</w:hyperlink>",
+                    id,
+                    self.runs(
+                        contents,
+                        RunStyle {
+                            underline: true,
+                            ..style
+                        }
+                    )
+                )
+            }
+            HTML::Italics(contents) => self.runs(
+                contents,
+                RunStyle {
+                    italic: true,
+                    ..style
+                },
+            ),
+            HTML::Bold(contents) => self.runs(
+                contents,
+                RunStyle {
+                    bold: true,
+                    ..style
+                },
+            ),
+            HTML::ColoredForeground(contents, color) => self.runs(
+                contents,
+                RunStyle {
+                    color: Some(*color),
+                    ..style
+                },
+            ),
+            HTML::ColoredBackground(contents, color) => self.runs(
+                contents,
+                RunStyle {
+                    shading: Some(*color),
+                    ..style
+                },
+            ),
+            other => other
+                .children()
+                .into_iter()
+                .map(|child| self.runs(child, style))
+                .collect(),
+        }
+    }
+
+    fn run(&self, text: &str, style: RunStyle) -> String {
+        if text.is_empty() {
+            return String::new();
+        }
+        // The properties must be in the order the schema lists them
+        let mut properties = String::new();
+        if style.bold {
+            properties.push_str("<w:b/>");
+        }
+        if style.italic {
+            properties.push_str("<w:i/>");
+        }
+        if let Some(color) = style.color {
+            properties.push_str(&format!("<w:color w:val=\"{}\"/>", hex(self.theme, color)));
+        }
+        if style.underline {
+            properties.push_str("<w:u w:val=\"single\"/>");
+        }
+        if let Some(shading) = style.shading {
+            properties.push_str(&format!(
+                "<w:shd w:val=\"clear\" w:color=\"auto\" w:fill=\"{}\"/>",
+                hex(self.theme, shading)
+            ));
+        }
+        if !properties.is_empty() {
+            properties = format!("<w:rPr>{}</w:rPr>", properties);
+        }
+        format!(
+            "<w:r>{}<w:t xml:space=\"preserve\">{}</w:t></w:r>",
+            properties,
+            escape(text)
+        )
+    }
+
+    fn paragraph(&mut self, style: Option<&str>, align: Option<&str>, runs: &str) -> String {
+        let mut properties = String::new();
+        if let Some(style) = style {
+            properties.push_str(&format!("<w:pStyle w:val=\"{}\"/>", style));
+        }
+        if let Some((list, level)) = self.numbered.take() {
+            properties.push_str(&format!(
+                "<w:numPr><w:ilvl w:val=\"{}\"/><w:numId w:val=\"{}\"/></w:numPr>",
+                level.min(8),
+                list
+            ));
+        }
+        if let Some(align) = align {
+            properties.push_str(&format!("<w:jc w:val=\"{}\"/>", align));
+        }
+        if !properties.is_empty() {
+            properties = format!("<w:pPr>{}</w:pPr>", properties);
+        }
+        format!("<w:p>{}{}</w:p>", properties, runs)
+    }
+
+    // A table of one row, splitting `width` between the cells in proportion
+    // to their spans. Only sections have borders.
+    fn table(
+        &mut self,
+        cells: Vec<(u32, &HTML)>,
+        style: RunStyle,
+        align: Option<&'static str>,
+        width: u32,
+        border: Option<&str>,
+    ) -> String {
+        let total: u32 = cells.iter().map(|(span, _)| span).sum::<u32>().max(1);
+        let widths: Vec<u32> = cells.iter().map(|(span, _)| width * span / total).collect();
+        let padding = if border.is_some() { SECTION_PADDING } else { 0 };
+
+        let mut xml = format!("<w:tbl><w:tblPr><w:tblW w:w=\"{}\" w:type=\"dxa\"/>", width);
+        if let Some(border) = border {
+            xml.push_str("<w:tblBorders>");
+            for side in &["top", "left", "bottom", "right"] {
+                xml.push_str(&format!(
+                    "<w:{} w:val=\"single\" w:sz=\"4\" w:space=\"0\" w:color=\"{}\"/>",
+                    side, border
+                ));
+            }
+            xml.push_str("</w:tblBorders>");
+        }
+        xml.push_str(&format!(
+            "<w:tblLayout w:type=\"fixed\"/><w:tblCellMar><w:top w:w=\"{p}\" w:type=\"dxa\"/><w:left w:w=\"{p}\" w:type=\"dxa\"/><w:bottom w:w=\"{p}\" w:type=\"dxa\"/><w:right w:w=\"{p}\" w:type=\"dxa\"/></w:tblCellMar></w:tblPr><w:tblGrid>",
+            p = padding
+        ));
+        for width in &widths {
+            xml.push_str(&format!("<w:gridCol w:w=\"{}\"/>", width));
+        }
+        xml.push_str("</w:tblGrid><w:tr>");
+
+        // A list item's number belongs to the first paragraph of the table
+        let numbered = self.numbered.take();
+        for (i, ((_, item), width)) in cells.iter().zip(widths).enumerate() {
+            if i == 0 {
+                self.numbered = numbered;
+            }
+            let mut contents = self.block(item, style, align, width.saturating_sub(2 * padding));
+            // Every cell must end with a paragraph
+            if !contents.ends_with("</w:p>") {
+                contents.push_str("<w:p/>");
+            }
+            xml.push_str(&format!(
+                "<w:tc><w:tcPr><w:tcW w:w=\"{}\" w:type=\"dxa\"/></w:tcPr>{}</w:tc>",
+                width, contents
+            ));
+        }
+        self.numbered = None;
+
+        // Tables next to each other would be merged into one without a
+        // paragraph between them
+        xml + "</w:tr></w:tbl><w:p><w:pPr><w:spacing w:after=\"0\"/></w:pPr></w:p>"
+    }
+
+    fn relationships(&self) -> String {
+        let mut xml = format!(
+            "{}<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">",
+            XML_DECLARATION
+        );
+        for (i, (kind, target)) in RELATIONSHIPS.iter().enumerate() {
+            xml.push_str(&format!(
+                "<Relationship Id=\"rId{}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/{}\" Target=\"{}\"/>",
+                i + 1,
+                kind,
+                target
+            ));
+        }
+        for (i, url) in self.links.iter().enumerate() {
+            xml.push_str(&format!(
+                "<Relationship Id=\"rId{}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink\" Target=\"{}\" TargetMode=\"External\"/>",
+                RELATIONSHIPS.len() + i + 1,
+                escape(url)
+            ));
+        }
+        xml + "</Relationships>"
+    }
+
+    // One abstract numbering for bullets and one for numbers, and an
+    // instance of one of them for every list
+    fn numbering(&self) -> String {
+        let mut xml = format!("{}<w:numbering {}>", XML_DECLARATION, WORD_NAMESPACES);
+        for &(id, ordered) in [(0, false), (1, true)].iter() {
+            xml.push_str(&format!("<w:abstractNum w:abstractNumId=\"{}\">", id));
+            for level in 0..9 {
+                let (format, text) = if ordered {
+                    ("decimal", format!("%{}.", level + 1))
+                } else {
+                    ("bullet", String::from("\u{2022}"))
+                };
+                xml.push_str(&format!(
+                    "<w:lvl w:ilvl=\"{}\"><w:start w:val=\"1\"/><w:numFmt w:val=\"{}\"/><w:lvlText w:val=\"{}\"/><w:lvlJc w:val=\"left\"/><w:pPr><w:ind w:left=\"{}\" w:hanging=\"360\"/></w:pPr></w:lvl>",
+                    level,
+                    format,
+                    text,
+                    360 * (level + 1)
+                ));
+            }
+            xml.push_str("</w:abstractNum>");
+        }
+        for (i, ordered) in self.lists.iter().enumerate() {
+            xml.push_str(&format!(
+                "<w:num w:numId=\"{}\"><w:abstractNumId w:val=\"{}\"/><w:lvlOverride w:ilvl=\"0\"><w:startOverride w:val=\"1\"/></w:lvlOverride></w:num>",
+                i + 1,
+                *ordered as u8
+            ));
+        }
+        xml + "</w:numbering>"
+    }
+}
+
+// Word wants colors as hex digits without the leading `#`
+fn hex(theme: &dyn Theme, color: Color) -> String {
+    theme
+        .get_color_hex(color)
+        .trim_start_matches('#')
+        .to_uppercase()
+}
+
+fn styles(theme: &dyn Theme) -> String {
+    format!(
+        "{}<w:styles {}><w:docDefaults><w:rPrDefault><w:rPr><w:color w:val=\"{}\"/><w:sz w:val=\"{}\"/><w:szCs w:val=\"{}\"/></w:rPr></w:rPrDefault><w:pPrDefault><w:pPr><w:spacing w:after=\"80\" w:line=\"259\" w:lineRule=\"auto\"/></w:pPr></w:pPrDefault></w:docDefaults>\
+<w:style w:type=\"paragraph\" w:default=\"1\" w:styleId=\"Normal\"><w:name w:val=\"Normal\"/></w:style>\
+<w:style w:type=\"paragraph\" w:styleId=\"Title\"><w:name w:val=\"Title\"/><w:basedOn w:val=\"Normal\"/><w:next w:val=\"Normal\"/><w:pPr><w:spacing w:after=\"120\"/></w:pPr><w:rPr><w:b/><w:sz w:val=\"{}\"/><w:szCs w:val=\"{}\"/></w:rPr></w:style>\
+<w:style w:type=\"paragraph\" w:styleId=\"Heading1\"><w:name w:val=\"heading 1\"/><w:basedOn w:val=\"Normal\"/><w:next w:val=\"Normal\"/><w:pPr><w:keepNext/><w:spacing w:before=\"120\" w:after=\"120\"/><w:outlineLvl w:val=\"0\"/></w:pPr><w:rPr><w:sz w:val=\"{}\"/><w:szCs w:val=\"{}\"/></w:rPr></w:style></w:styles>",
+        XML_DECLARATION,
+        WORD_NAMESPACES,
+        hex(theme, Color::DefaultForeground),
+        FONT_SIZE,
+        FONT_SIZE,
+        TITLE_FONT_SIZE,
+        TITLE_FONT_SIZE,
+        SECTION_TITLE_FONT_SIZE,
+        SECTION_TITLE_FONT_SIZE
+    )
+}
+
+fn core_properties(title: &str) -> String {
+    format!(
+        "{}<cp:coreProperties xmlns:cp=\"http://schemas.openxmlformats.org/package/2006/metadata/core-properties\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\"><dc:title>{}</dc:title></cp:coreProperties>",
+        XML_DECLARATION,
+        escape(title)
+    )
+}
+
+const XML_DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n";
+
+const CONTENT_TYPES: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\"><Default Extension=\"rels\" ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/><Default Extension=\"xml\" ContentType=\"application/xml\"/><Override PartName=\"/word/document.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml\"/><Override PartName=\"/word/styles.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml\"/><Override PartName=\"/word/numbering.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml\"/><Override PartName=\"/word/settings.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.wordprocessingml.settings+xml\"/><Override PartName=\"/docProps/core.xml\" ContentType=\"application/vnd.openxmlformats-package.core-properties+xml\"/></Types>";
+
+const PACKAGE_RELATIONSHIPS: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\"><Relationship Id=\"rId1\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument\" Target=\"word/document.xml\"/><Relationship Id=\"rId2\" Type=\"http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties\" Target=\"docProps/core.xml\"/></Relationships>";
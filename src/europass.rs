@@ -0,0 +1,1238 @@
+use super::{
+    date::YEARS,
+    html::escape,
+    resume::{non_empty, profile_name, profile_url},
+    Award, Certification, ContactInfo, Conversion, CustomEntry, CustomSection, Date, Degree,
+    DocumentError, Education, Layout, Proficiency, Project, Publication, Resume, Work,
+};
+use core::iter;
+use roxmltree::{Document, Node};
+
+// Europass CVs are XML documents following version 3.4 of the schema
+// published by Cedefop. Longer text, like the activities of a job, is kept
+// as a fragment of HTML inside the XML.
+
+const NAMESPACE: &str = "http://europass.cedefop.europa.eu/Europass";
+const SCHEMA_LOCATION: &str = "http://europass.cedefop.europa.eu/Europass http://europass.cedefop.europa.eu/xml/v3.4.0/EuropassSchema.xsd";
+
+// The languages Europass lists, by their ISO 639-1 code. A skill naming one
+// is written as a foreign language, rated on the CEFR scale.
+const LANGUAGES: [(&str, &str); 33] = [
+    ("ar", "Arabic"),
+    ("bg", "Bulgarian"),
+    ("cs", "Czech"),
+    ("da", "Danish"),
+    ("de", "German"),
+    ("el", "Greek"),
+    ("en", "English"),
+    ("es", "Spanish"),
+    ("et", "Estonian"),
+    ("fi", "Finnish"),
+    ("fr", "French"),
+    ("ga", "Irish"),
+    ("hi", "Hindi"),
+    ("hr", "Croatian"),
+    ("hu", "Hungarian"),
+    ("is", "Icelandic"),
+    ("it", "Italian"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+    ("lt", "Lithuanian"),
+    ("lv", "Latvian"),
+    ("mt", "Maltese"),
+    ("nl", "Dutch"),
+    ("no", "Norwegian"),
+    ("pl", "Polish"),
+    ("pt", "Portuguese"),
+    ("ro", "Romanian"),
+    ("ru", "Russian"),
+    ("sk", "Slovak"),
+    ("sl", "Slovenian"),
+    ("sv", "Swedish"),
+    ("tr", "Turkish"),
+    ("zh", "Chinese"),
+];
+
+// The five abilities a foreign language is rated on
+const LANGUAGE_ABILITIES: [&str; 5] = [
+    "Listening",
+    "Reading",
+    "SpokenInteraction",
+    "SpokenProduction",
+    "Writing",
+];
+
+// The kinds of achievement with a place in a `Resume`, by their code and the
+// title Europass shows for them
+const PROJECTS: (&str, &str) = ("projects", "Projects");
+const CERTIFICATIONS: (&str, &str) = ("certifications", "Certifications");
+const AWARDS: (&str, &str) = ("honors_awards", "Honours and awards");
+const PUBLICATIONS: (&str, &str) = ("publications", "Publications");
+
+impl Degree {
+    /// The level of the European Qualifications Framework, from 1 to 8,
+    /// that this degree is at
+    pub fn eqf_level(self) -> u8 {
+        match self {
+            Self::HighSchoolDiploma => 4,
+            Self::Associates => 5,
+            Self::Bachelors => 6,
+            Self::Masters => 7,
+            Self::PhD => 8,
+        }
+    }
+
+    /// The degree at a level of the European Qualifications Framework.
+    /// Levels 1 to 3 come before the end of secondary school, so have none.
+    pub fn from_eqf_level(level: u8) -> Option<Self> {
+        match level {
+            4 => Some(Self::HighSchoolDiploma),
+            5 => Some(Self::Associates),
+            6 => Some(Self::Bachelors),
+            7 => Some(Self::Masters),
+            8 => Some(Self::PhD),
+            _ => None,
+        }
+    }
+}
+
+impl Proficiency {
+    /// The level of the Common European Framework of Reference for
+    /// Languages, from `A1` to `C2`, for someone this proficient in a
+    /// language. Knowing none of a language has no level.
+    pub fn cefr_level(self) -> Option<&'static str> {
+        match self {
+            Self::None => None,
+            Self::Barely => Some("A2"),
+            Self::Some => Some("B1"),
+            Self::Strong => Some("B2"),
+            Self::Expert => Some("C2"),
+        }
+    }
+
+    /// The proficiency of someone at a CEFR level, like `B2`
+    pub fn from_cefr_level(level: &str) -> Option<Self> {
+        match level.trim().to_uppercase().as_str() {
+            "A1" | "A2" => Some(Self::Barely),
+            "B1" => Some(Self::Some),
+            "B2" => Some(Self::Strong),
+            "C1" | "C2" => Some(Self::Expert),
+            _ => None,
+        }
+    }
+}
+
+impl Resume {
+    /// Read a Europass CV written in XML. Anything without a place in a
+    /// `Resume`, such as `Identification/Demographics` or a driving
+    /// licence, is listed in the conversion by its path in the document.
+    pub fn from_europass(xml: &str) -> Result<Conversion<Self>, DocumentError> {
+        let document = Document::parse(xml).map_err(|e| DocumentError::Parse {
+            path: String::from("."),
+            message: e.to_string(),
+        })?;
+        let root = document.root_element();
+        if root.tag_name().name() != "SkillsPassport" {
+            return Err(DocumentError::Parse {
+                path: String::from("."),
+                message: format!(
+                    "expected a Europass CV, found a <{}> document",
+                    root.tag_name().name()
+                ),
+            });
+        }
+        let learner = child(root, "LearnerInfo").ok_or_else(|| DocumentError::Parse {
+            path: String::from("SkillsPassport"),
+            message: String::from("missing LearnerInfo"),
+        })?;
+
+        let mut unmapped = Vec::new();
+        report(
+            &mut unmapped,
+            "",
+            root,
+            &["DocumentInfo", "PrintingPreferences", "LearnerInfo"],
+        );
+        report(
+            &mut unmapped,
+            "LearnerInfo",
+            learner,
+            &[
+                "Identification",
+                "Headline",
+                "WorkExperienceList",
+                "EducationList",
+                "Skills",
+                "AchievementList",
+            ],
+        );
+
+        let mut resume = Resume {
+            first_name: String::new(),
+            last_name: String::new(),
+            description: String::new(),
+            contact_info: ContactInfo::default(),
+            profession: String::new(),
+            skills: Vec::new(),
+            education: Vec::new(),
+            work_experience: Vec::new(),
+            projects: Vec::new(),
+            certifications: Vec::new(),
+            awards: Vec::new(),
+            publications: Vec::new(),
+            custom_sections: Vec::new(),
+            layout: Layout::default(),
+        };
+
+        if let Some(identification) = child(learner, "Identification") {
+            let path = "LearnerInfo/Identification";
+            report(
+                &mut unmapped,
+                path,
+                identification,
+                &["PersonName", "ContactInfo"],
+            );
+            if let Some(name) = child(identification, "PersonName") {
+                report(
+                    &mut unmapped,
+                    &format!("{}/PersonName", path),
+                    name,
+                    &["FirstName", "Surname"],
+                );
+                resume.first_name = text(name, "FirstName").unwrap_or_default();
+                resume.last_name = text(name, "Surname").unwrap_or_default();
+            }
+            if let Some(contact) = child(identification, "ContactInfo") {
+                resume.contact_info =
+                    contact_info(&mut unmapped, &format!("{}/ContactInfo", path), contact);
+            }
+        }
+
+        // A personal statement is the closest Europass has to a description
+        if let Some(headline) = child(learner, "Headline") {
+            let label = text(headline, "Description/Label").unwrap_or_default();
+            match text(headline, "Type/Code").as_deref() {
+                Some("personal_statement") => resume.description = label,
+                _ => resume.profession = label,
+            }
+        }
+
+        for (i, job) in elements(learner, "WorkExperienceList/WorkExperience").enumerate() {
+            let path = format!("LearnerInfo/WorkExperienceList/WorkExperience[{}]", i + 1);
+            report(
+                &mut unmapped,
+                &path,
+                job,
+                &["Period", "Position", "Activities", "Employer"],
+            );
+            let (start, end) = period(&mut unmapped, &path, job);
+            let start = match start {
+                Some(start) => start,
+                // A job can't be shown without knowing when it started
+                None => {
+                    unmapped.push(path);
+                    continue;
+                }
+            };
+            if let Some(employer) = child(job, "Employer") {
+                report(
+                    &mut unmapped,
+                    &format!("{}/Employer", path),
+                    employer,
+                    &["Name"],
+                );
+            }
+            let (paragraphs, highlights) = rich_text(&text(job, "Activities").unwrap_or_default());
+            resume.work_experience.push(Work {
+                start,
+                end: end.unwrap_or(Date::Present),
+                position: text(job, "Position/Label").unwrap_or_default(),
+                company: text(job, "Employer/Name").unwrap_or_default(),
+                description: paragraphs.join(" "),
+                highlights,
+            });
+        }
+
+        for (i, school) in elements(learner, "EducationList/Education").enumerate() {
+            let path = format!("LearnerInfo/EducationList/Education[{}]", i + 1);
+            report(
+                &mut unmapped,
+                &path,
+                school,
+                &["Period", "Title", "Organisation", "Level", "Field"],
+            );
+            let (start, end) = period(&mut unmapped, &path, school);
+            let start = match start {
+                Some(start) => start,
+                None => {
+                    unmapped.push(path);
+                    continue;
+                }
+            };
+            let degree = text(school, "Level/Code").and_then(|level| {
+                let degree = level.parse().ok().and_then(Degree::from_eqf_level);
+                if degree.is_none() {
+                    unmapped.push(format!("{}/Level", path));
+                }
+                degree
+            });
+            // A title that only names the degree and field, like the ones
+            // `to_europass` writes, says nothing new
+            let field = text(school, "Field/Label");
+            let generated: Vec<String> = degree
+                .into_iter()
+                .flat_map(|degree| {
+                    iter::once(degree.to_string()).chain(
+                        field
+                            .as_ref()
+                            .map(|field| format!("{} in {}", degree, field)),
+                    )
+                })
+                .collect();
+            let title = text(school, "Title").filter(|title| !generated.contains(title));
+            // Without a field, the title of the qualification says what was studied
+            let field = match (field, title) {
+                (Some(field), Some(_)) => {
+                    unmapped.push(format!("{}/Title", path));
+                    Some(field)
+                }
+                (field, title) => field.or(title),
+            };
+            resume.education.push(Education {
+                start,
+                end: end.unwrap_or(Date::Present),
+                school: text(school, "Organisation/Name").unwrap_or_default(),
+                field,
+                degree,
+            });
+        }
+
+        if let Some(skills) = child(learner, "Skills") {
+            resume.skills = read_skills(&mut unmapped, "LearnerInfo/Skills", skills);
+        }
+
+        read_achievements(&mut unmapped, learner, &mut resume);
+
+        Ok(Conversion {
+            value: resume,
+            unmapped,
+        })
+    }
+
+    /// Write this resume as a Europass CV in XML. The description is only
+    /// kept when there is no profession to use as the headline, and the
+    /// layout is never kept, so either is listed in the conversion.
+    pub fn to_europass(&self) -> Conversion<String> {
+        let mut unmapped = Vec::new();
+        let mut xml = Writer::default();
+
+        xml.start(
+            "SkillsPassport",
+            &[
+                ("xmlns", NAMESPACE),
+                ("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance"),
+                ("xsi:schemaLocation", SCHEMA_LOCATION),
+                ("locale", "en"),
+            ],
+        );
+        xml.start("DocumentInfo", &[]);
+        xml.leaf("DocumentType", "ECV");
+        xml.leaf("XSDVersion", "V3.4");
+        xml.leaf("Generator", concat!("resume ", env!("CARGO_PKG_VERSION")));
+        xml.end();
+
+        xml.start("LearnerInfo", &[]);
+        self.write_identification(&mut xml);
+
+        if !self.profession.is_empty() {
+            write_headline(&mut xml, "position", &self.profession);
+            if !self.description.is_empty() {
+                unmapped.push(String::from("description"));
+            }
+        } else if !self.description.is_empty() {
+            write_headline(&mut xml, "personal_statement", &self.description);
+        }
+
+        if !self.work_experience.is_empty() {
+            xml.start("WorkExperienceList", &[]);
+            for job in &self.work_experience {
+                xml.start("WorkExperience", &[]);
+                write_period(&mut xml, job.start, job.end);
+                xml.start("Position", &[]);
+                xml.leaf("Label", &job.position);
+                xml.end();
+                let description: Vec<String> = non_empty(&job.description).into_iter().collect();
+                xml.leaf("Activities", &rich_text_html(&description, &job.highlights));
+                xml.start("Employer", &[]);
+                xml.leaf("Name", &job.company);
+                xml.end();
+                xml.end();
+            }
+            xml.end();
+        }
+
+        if !self.education.is_empty() {
+            xml.start("EducationList", &[]);
+            for school in &self.education {
+                xml.start("Education", &[]);
+                write_period(&mut xml, school.start, school.end);
+                let title = match (school.degree, &school.field) {
+                    (Some(degree), Some(field)) => Some(format!("{} in {}", degree, field)),
+                    (Some(degree), None) => Some(degree.to_string()),
+                    (None, field) => field.clone(),
+                };
+                if let Some(title) = title {
+                    xml.leaf("Title", &title);
+                }
+                xml.start("Organisation", &[]);
+                xml.leaf("Name", &school.school);
+                xml.end();
+                if let Some(degree) = school.degree {
+                    xml.start("Level", &[]);
+                    xml.leaf("Code", &degree.eqf_level().to_string());
+                    xml.end();
+                }
+                if let Some(field) = &school.field {
+                    xml.start("Field", &[]);
+                    xml.leaf("Label", field);
+                    xml.end();
+                }
+                xml.end();
+            }
+            xml.end();
+        }
+
+        self.write_skills(&mut xml);
+        self.write_achievements(&mut xml, &mut unmapped);
+        xml.end();
+        xml.end();
+
+        if self.layout != Layout::default() {
+            unmapped.push(String::from("layout"));
+        }
+
+        Conversion {
+            value: xml.finish(),
+            unmapped,
+        }
+    }
+
+    fn write_identification(&self, xml: &mut Writer) {
+        let contact = &self.contact_info;
+        xml.start("Identification", &[]);
+        xml.start("PersonName", &[]);
+        xml.leaf("FirstName", &self.first_name);
+        xml.leaf("Surname", &self.last_name);
+        xml.end();
+
+        xml.start("ContactInfo", &[]);
+        if let Some(email) = &contact.email {
+            xml.start("Email", &[]);
+            xml.leaf("Contact", email);
+            xml.end();
+        }
+        if let Some(phone) = &contact.phone {
+            xml.start("TelephoneList", &[]);
+            xml.start("Telephone", &[]);
+            xml.leaf("Contact", phone);
+            xml.end();
+            xml.end();
+        }
+        // Profiles are listed as websites, and told apart again by their URL
+        let websites: Vec<String> = contact
+            .website
+            .iter()
+            .cloned()
+            .chain(
+                contact
+                    .github
+                    .iter()
+                    .map(|github| profile_url(github, "github.com", "https://github.com/")),
+            )
+            .chain(contact.linkedin.iter().map(|linkedin| {
+                profile_url(linkedin, "linkedin.com", "https://www.linkedin.com/in/")
+            }))
+            .collect();
+        if !websites.is_empty() {
+            xml.start("WebsiteList", &[]);
+            for website in websites {
+                xml.start("Website", &[]);
+                xml.leaf("Contact", &website);
+                xml.end();
+            }
+            xml.end();
+        }
+        xml.end();
+        xml.end();
+    }
+
+    fn write_skills(&self, xml: &mut Writer) {
+        let mut languages = Vec::new();
+        let mut others = Vec::new();
+        for (name, level) in &self.skills {
+            let language = LANGUAGES
+                .iter()
+                .find(|(_, language)| language.eq_ignore_ascii_case(name.trim()));
+            match (language, level) {
+                // Knowing none of a language has no CEFR level to write
+                (Some(_), Some(Proficiency::None)) | (None, _) => others.push(match level {
+                    Some(level) => format!("{} ({})", name, level),
+                    None => name.clone(),
+                }),
+                (Some((code, _)), level) => languages.push((*code, name, *level)),
+            }
+        }
+        if languages.is_empty() && others.is_empty() {
+            return;
+        }
+
+        xml.start("Skills", &[]);
+        if !languages.is_empty() {
+            xml.start("Linguistic", &[]);
+            xml.start("ForeignLanguageList", &[]);
+            for (code, name, level) in languages {
+                xml.start("ForeignLanguage", &[]);
+                xml.start("Description", &[]);
+                xml.leaf("Code", code);
+                xml.leaf("Label", name);
+                xml.end();
+                if let Some(level) = level.and_then(Proficiency::cefr_level) {
+                    xml.start("ProficiencyLevel", &[]);
+                    for ability in LANGUAGE_ABILITIES.iter() {
+                        xml.leaf(ability, level);
+                    }
+                    xml.end();
+                }
+                xml.end();
+            }
+            xml.end();
+            xml.end();
+        }
+        if !others.is_empty() {
+            xml.start("JobRelated", &[]);
+            xml.leaf("Description", &rich_text_html(&[], &others));
+            xml.end();
+        }
+        xml.end();
+    }
+
+    fn write_achievements(&self, xml: &mut Writer, unmapped: &mut Vec<String>) {
+        let mut achievements = Vec::new();
+        for project in &self.projects {
+            achievements.push((
+                Some(PROJECTS.0),
+                PROJECTS.1,
+                Entry::html(
+                    &project.name,
+                    vec![
+                        ("Role", project.role.clone()),
+                        ("URL", project.url.clone()),
+                        ("Repository", project.repository.clone()),
+                        ("Start", project.start.map(|date| date.to_string())),
+                        ("End", project.end.map(|date| date.to_string())),
+                        ("Technologies", join(&project.technologies)),
+                    ],
+                    &project.highlights,
+                ),
+            ));
+        }
+        for certification in &self.certifications {
+            achievements.push((
+                Some(CERTIFICATIONS.0),
+                CERTIFICATIONS.1,
+                Entry::html(
+                    &certification.name,
+                    vec![
+                        ("Issuer", non_empty(&certification.issuer)),
+                        ("Credential ID", certification.credential_id.clone()),
+                        ("Issued", certification.issued.map(|date| date.to_string())),
+                        (
+                            "Expires",
+                            certification.expires.map(|date| date.to_string()),
+                        ),
+                        ("URL", certification.url.clone()),
+                    ],
+                    &[],
+                ),
+            ));
+        }
+        for award in &self.awards {
+            achievements.push((
+                Some(AWARDS.0),
+                AWARDS.1,
+                Entry::html(
+                    &award.title,
+                    vec![
+                        ("Issuer", award.issuer.clone()),
+                        ("Date", award.date.map(|date| date.to_string())),
+                        ("Summary", award.summary.clone()),
+                    ],
+                    &[],
+                ),
+            ));
+        }
+        for publication in &self.publications {
+            achievements.push((
+                Some(PUBLICATIONS.0),
+                PUBLICATIONS.1,
+                Entry::html(
+                    &publication.title,
+                    vec![
+                        ("Authors", join(&publication.authors)),
+                        ("Venue", publication.venue.clone()),
+                        ("Year", publication.year.map(|year| year.to_string())),
+                        ("DOI", publication.doi.clone()),
+                        ("URL", publication.url.clone()),
+                    ],
+                    &[],
+                ),
+            ));
+        }
+        for (i, section) in self.custom_sections.iter().enumerate() {
+            // Each entry is its own achievement, so a section without any is lost
+            if section.entries.is_empty() {
+                unmapped.push(format!("custom_sections[{}]", i));
+            }
+            for entry in &section.entries {
+                achievements.push((
+                    None,
+                    &section.title,
+                    Entry::html(
+                        &entry.heading,
+                        vec![
+                            ("Subheading", entry.subheading.clone()),
+                            ("Location", entry.location.clone()),
+                            ("Start", entry.start.map(|date| date.to_string())),
+                            ("End", entry.end.map(|date| date.to_string())),
+                        ],
+                        &entry.bullets,
+                    ),
+                ));
+            }
+        }
+        if achievements.is_empty() {
+            return;
+        }
+
+        xml.start("AchievementList", &[]);
+        for (code, label, description) in achievements {
+            xml.start("Achievement", &[]);
+            xml.start("Title", &[]);
+            if let Some(code) = code {
+                xml.leaf("Code", code);
+            }
+            xml.leaf("Label", label);
+            xml.end();
+            xml.leaf("Description", &description);
+            xml.end();
+        }
+        xml.end();
+    }
+}
+
+fn read_skills(
+    unmapped: &mut Vec<String>,
+    path: &str,
+    skills: Node,
+) -> Vec<(String, Option<Proficiency>)> {
+    let mut result = Vec::new();
+    report(
+        unmapped,
+        path,
+        skills,
+        &[
+            "Linguistic",
+            "Communication",
+            "Organisational",
+            "JobRelated",
+            "Computer",
+            "Other",
+        ],
+    );
+
+    // Skills other than languages are described in free text, so only the
+    // items of its lists can be read as skills
+    for kind in &[
+        "Communication",
+        "Organisational",
+        "JobRelated",
+        "Computer",
+        "Other",
+    ] {
+        let node = match child(skills, kind) {
+            Some(node) => node,
+            None => continue,
+        };
+        let path = format!("{}/{}", path, kind);
+        report(unmapped, &path, node, &["Description"]);
+        let (paragraphs, items) = rich_text(&text(node, "Description").unwrap_or_default());
+        if !paragraphs.is_empty() {
+            unmapped.push(format!("{}/Description", path));
+        }
+        result.extend(items.into_iter().map(|item| skill(&item)));
+    }
+
+    // Languages come after every other skill, like on most resumes
+    if let Some(linguistic) = child(skills, "Linguistic") {
+        let path = format!("{}/Linguistic", path);
+        report(
+            unmapped,
+            &path,
+            linguistic,
+            &["MotherTongueList", "ForeignLanguageList"],
+        );
+        // Nobody is more fluent than in their mother tongue
+        for tongue in elements(linguistic, "MotherTongueList/MotherTongue") {
+            if let Some(name) = language_name(tongue) {
+                result.push((name, Some(Proficiency::Expert)));
+            }
+        }
+        for (i, language) in elements(linguistic, "ForeignLanguageList/ForeignLanguage").enumerate()
+        {
+            let path = format!("{}/ForeignLanguageList/ForeignLanguage[{}]", path, i + 1);
+            report(
+                unmapped,
+                &path,
+                language,
+                &["Description", "ProficiencyLevel"],
+            );
+            let name = match language_name(language) {
+                Some(name) => name,
+                None => {
+                    unmapped.push(path);
+                    continue;
+                }
+            };
+            // A language is only as strong as the weakest ability rated
+            let levels: Vec<String> = LANGUAGE_ABILITIES
+                .iter()
+                .filter_map(|ability| text(language, &format!("ProficiencyLevel/{}", ability)))
+                .collect();
+            let level = levels
+                .iter()
+                .map(|level| Proficiency::from_cefr_level(level))
+                .collect::<Option<Vec<_>>>()
+                .and_then(|levels| levels.into_iter().min());
+            if level.is_none() && !levels.is_empty() {
+                unmapped.push(format!("{}/ProficiencyLevel", path));
+            }
+            result.push((name, level));
+        }
+    }
+    result
+}
+
+// Read a skill written like `Rust (Expert)`, or with no level at all
+fn skill(text: &str) -> (String, Option<Proficiency>) {
+    if let Some((name, level)) = text
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once(" ("))
+    {
+        if let Some(level) = Proficiency::all()
+            .iter()
+            .find(|proficiency| proficiency.to_string().eq_ignore_ascii_case(level))
+        {
+            return (name.trim().to_string(), Some(*level));
+        }
+    }
+    (text.to_string(), None)
+}
+
+// The name of a language, from its label or else its code
+fn language_name(language: Node) -> Option<String> {
+    text(language, "Description/Label").or_else(|| {
+        let code = text(language, "Description/Code")?;
+        Some(
+            LANGUAGES
+                .iter()
+                .find(|(known, _)| known.eq_ignore_ascii_case(&code))
+                .map_or(code, |(_, name)| name.to_string()),
+        )
+    })
+}
+
+fn read_achievements(unmapped: &mut Vec<String>, learner: Node, resume: &mut Resume) {
+    for (i, achievement) in elements(learner, "AchievementList/Achievement").enumerate() {
+        let path = format!("LearnerInfo/AchievementList/Achievement[{}]", i + 1);
+        report(unmapped, &path, achievement, &["Title", "Description"]);
+        let code = text(achievement, "Title/Code").unwrap_or_default();
+        let mut entry = Entry::parse(&text(achievement, "Description").unwrap_or_default());
+        let date = |entry: &mut Entry, unmapped: &mut Vec<String>, key: &str| {
+            let value = entry.take(key)?;
+            match value.parse() {
+                Ok(date) => Some(date),
+                Err(_) => {
+                    unmapped.push(format!("{}/Description", path));
+                    None
+                }
+            }
+        };
+
+        match code.as_str() {
+            code if code == PROJECTS.0 => resume.projects.push(Project {
+                name: entry.heading.clone(),
+                role: entry.take("Role"),
+                url: entry.take("URL"),
+                repository: entry.take("Repository"),
+                start: date(&mut entry, unmapped, "Start"),
+                end: date(&mut entry, unmapped, "End"),
+                technologies: split(entry.take("Technologies")),
+                highlights: entry.items.drain(..).collect(),
+            }),
+            code if code == CERTIFICATIONS.0 => resume.certifications.push(Certification {
+                name: entry.heading.clone(),
+                issuer: entry.take("Issuer").unwrap_or_default(),
+                credential_id: entry.take("Credential ID"),
+                issued: date(&mut entry, unmapped, "Issued"),
+                expires: date(&mut entry, unmapped, "Expires"),
+                url: entry.take("URL"),
+            }),
+            code if code == AWARDS.0 => resume.awards.push(Award {
+                title: entry.heading.clone(),
+                issuer: entry.take("Issuer"),
+                date: date(&mut entry, unmapped, "Date"),
+                summary: entry.take("Summary"),
+            }),
+            code if code == PUBLICATIONS.0 => {
+                let year = entry.take("Year");
+                let parsed = year.as_ref().and_then(|year| year.parse().ok());
+                if year.is_some() && parsed.is_none() {
+                    unmapped.push(format!("{}/Description", path));
+                }
+                resume.publications.push(Publication {
+                    title: entry.heading.clone(),
+                    authors: split(entry.take("Authors")),
+                    venue: entry.take("Venue"),
+                    year: parsed,
+                    doi: entry.take("DOI"),
+                    url: entry.take("URL"),
+                })
+            }
+            // Any other kind of achievement, like conferences or
+            // memberships, is kept in a custom section of its own
+            _ => {
+                let title = text(achievement, "Title/Label").unwrap_or_else(|| {
+                    let mut title = code.replace('_', " ");
+                    if let Some(first) = title.get_mut(..1) {
+                        first.make_ascii_uppercase();
+                    }
+                    title
+                });
+                let subheading = entry.take("Subheading");
+                let location = entry.take("Location");
+                let start = date(&mut entry, unmapped, "Start");
+                let end = date(&mut entry, unmapped, "End");
+                // Paragraphs that are not fields are kept as bullets
+                let mut bullets: Vec<String> = entry.rest().collect();
+                bullets.append(&mut entry.items);
+                let custom = CustomEntry {
+                    heading: entry.heading,
+                    subheading,
+                    start,
+                    end,
+                    location,
+                    bullets,
+                };
+                match resume
+                    .custom_sections
+                    .iter_mut()
+                    .find(|section| section.title == title)
+                {
+                    Some(section) => section.entries.push(custom),
+                    None => resume.custom_sections.push(CustomSection {
+                        title,
+                        entries: vec![custom],
+                    }),
+                }
+                continue;
+            }
+        }
+
+        if entry.rest().next().is_some() || !entry.items.is_empty() {
+            unmapped.push(format!("{}/Description", path));
+        }
+    }
+}
+
+// The text of an achievement, as a heading in its first paragraph followed
+// by paragraphs of `Key: value` fields and then a list
+struct Entry {
+    heading: String,
+    paragraphs: Vec<String>,
+    items: Vec<String>,
+}
+
+impl Entry {
+    fn parse(html: &str) -> Self {
+        let (mut paragraphs, items) = rich_text(html);
+        let heading = if paragraphs.is_empty() {
+            String::new()
+        } else {
+            paragraphs.remove(0)
+        };
+        Self {
+            heading,
+            paragraphs,
+            items,
+        }
+    }
+
+    fn html(heading: &str, fields: Vec<(&str, Option<String>)>, items: &[String]) -> String {
+        let paragraphs: Vec<String> = Some(heading.to_string())
+            .into_iter()
+            .chain(
+                fields
+                    .into_iter()
+                    .filter_map(|(key, value)| Some(format!("{}: {}", key, value?))),
+            )
+            .collect();
+        rich_text_html(&paragraphs, items)
+    }
+
+    // Remove the field named `key`, returning its value
+    fn take(&mut self, key: &str) -> Option<String> {
+        let i = self.paragraphs.iter().position(|paragraph| {
+            paragraph
+                .split_once(": ")
+                .is_some_and(|(name, _)| name.eq_ignore_ascii_case(key))
+        })?;
+        let paragraph = self.paragraphs.remove(i);
+        paragraph
+            .split_once(": ")
+            .map(|(_, value)| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+
+    // The paragraphs that were not taken as fields
+    fn rest(&mut self) -> impl Iterator<Item = String> + '_ {
+        self.paragraphs.drain(..)
+    }
+}
+
+// Split Europass rich text into its paragraphs and the items of its lists,
+// with the markup removed. Text without any markup is split into lines.
+fn rich_text(html: &str) -> (Vec<String>, Vec<String>) {
+    if !html.contains('<') {
+        let lines = html
+            .lines()
+            .map(|line| unescape(line.trim()))
+            .filter(|line| !line.is_empty())
+            .collect();
+        return (lines, Vec::new());
+    }
+
+    let mut paragraphs = Vec::new();
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut in_item = false;
+    let mut flush = |current: &mut String, in_item: bool| {
+        let text = unescape(&current.split_whitespace().collect::<Vec<_>>().join(" "));
+        if !text.is_empty() {
+            if in_item {
+                items.push(text);
+            } else {
+                paragraphs.push(text);
+            }
+        }
+        current.clear();
+    };
+
+    let mut rest = html;
+    while let Some(open) = rest.find('<') {
+        current.push_str(&rest[..open]);
+        let close = match rest[open..].find('>') {
+            Some(close) => open + close,
+            None => break,
+        };
+        let tag = &rest[open + 1..close];
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|ch: char| ch.is_whitespace() || ch == '/')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        rest = &rest[close + 1..];
+        match name.as_str() {
+            "li" => {
+                flush(&mut current, in_item);
+                in_item = !closing;
+            }
+            "p" | "br" | "div" | "ul" | "ol" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                flush(&mut current, in_item)
+            }
+            // Inline markup, like bold text or links, keeps only its text
+            _ => {}
+        }
+    }
+    current.push_str(rest);
+    flush(&mut current, in_item);
+    (paragraphs, items)
+}
+
+// Write paragraphs followed by a list as Europass rich text
+fn rich_text_html(paragraphs: &[String], items: &[String]) -> String {
+    let mut html: String = paragraphs
+        .iter()
+        .map(|paragraph| format!("<p>{}</p>", escape(paragraph)))
+        .collect();
+    if !items.is_empty() {
+        html.push_str("<ul>");
+        for item in items {
+            html.push_str(&format!("<li>{}</li>", escape(item)));
+        }
+        html.push_str("</ul>");
+    }
+    html
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn contact_info(unmapped: &mut Vec<String>, path: &str, contact: Node) -> ContactInfo {
+    report(
+        unmapped,
+        path,
+        contact,
+        &["Email", "TelephoneList", "WebsiteList"],
+    );
+    let mut info = ContactInfo {
+        email: text(contact, "Email/Contact"),
+        ..ContactInfo::default()
+    };
+
+    for (i, phone) in elements(contact, "TelephoneList/Telephone").enumerate() {
+        match text(phone, "Contact") {
+            Some(phone) if info.phone.is_none() => info.phone = Some(phone),
+            _ => unmapped.push(format!("{}/TelephoneList/Telephone[{}]", path, i + 1)),
+        }
+    }
+
+    for (i, website) in elements(contact, "WebsiteList/Website").enumerate() {
+        let url = match text(website, "Contact") {
+            Some(url) => url,
+            None => continue,
+        };
+        let (slot, profile) = if url.contains("github.com") {
+            (
+                &mut info.github,
+                profile_name(&url, "github.com").to_string(),
+            )
+        } else if url.contains("linkedin.com") {
+            (
+                &mut info.linkedin,
+                profile_name(&url, "linkedin.com/in").to_string(),
+            )
+        } else {
+            (&mut info.website, url.clone())
+        };
+        if slot.is_none() {
+            *slot = Some(profile);
+        } else {
+            unmapped.push(format!("{}/WebsiteList/Website[{}]", path, i + 1));
+        }
+    }
+    info
+}
+
+// Read the dates of the `Period` of `node`. Something still going on has
+// no `To`, but a `Current` of true.
+fn period(unmapped: &mut Vec<String>, path: &str, node: Node) -> (Option<Date>, Option<Date>) {
+    let period = match child(node, "Period") {
+        Some(period) => period,
+        None => return (None, None),
+    };
+    let path = format!("{}/Period", path);
+    let start = child(period, "From").and_then(|from| date(unmapped, &path, from));
+    let end = if text(period, "Current").as_deref() == Some("true") {
+        Some(Date::Present)
+    } else {
+        child(period, "To").and_then(|to| date(unmapped, &path, to))
+    };
+    (start, end)
+}
+
+// Read a date like `<From year="2021" month="--03"/>`. Days are dropped,
+// since resumes only show months.
+fn date(unmapped: &mut Vec<String>, path: &str, node: Node) -> Option<Date> {
+    let year = node
+        .attribute("year")
+        .and_then(|year| year.trim().parse().ok())
+        .filter(|year| YEARS.contains(year));
+    let month = node.attribute("month").map(|month| {
+        month
+            .trim()
+            .trim_start_matches('-')
+            .parse()
+            .ok()
+            .filter(|month| (1..=12).contains(month))
+    });
+    match (year, month) {
+        (Some(year), None) => Some(Date::Year(year)),
+        (Some(year), Some(Some(month))) => Some(Date::Month(year, month)),
+        _ => {
+            unmapped.push(format!("{}/{}", path, node.tag_name().name()));
+            None
+        }
+    }
+}
+
+fn write_period(xml: &mut Writer, start: Date, end: Date) {
+    xml.start("Period", &[]);
+    for &(tag, date) in [("From", start), ("To", end)].iter() {
+        match date {
+            Date::Year(year) => xml.empty(tag, &[("year", &year.to_string())]),
+            Date::Month(year, month) => xml.empty(
+                tag,
+                &[
+                    ("year", &year.to_string()),
+                    ("month", &format!("--{:02}", month)),
+                ],
+            ),
+            Date::Present => xml.leaf("Current", "true"),
+        }
+    }
+    xml.end();
+}
+
+fn write_headline(xml: &mut Writer, code: &str, label: &str) {
+    xml.start("Headline", &[]);
+    xml.start("Type", &[]);
+    xml.leaf("Code", code);
+    xml.end();
+    xml.start("Description", &[]);
+    xml.leaf("Label", label);
+    xml.end();
+    xml.end();
+}
+
+// The first child element of `node` named `name`
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|child| child.is_element() && child.tag_name().name() == name)
+}
+
+// Every element at the end of a path of child elements, like
+// `WorkExperienceList/WorkExperience`
+fn elements<'a, 'input>(
+    node: Node<'a, 'input>,
+    path: &str,
+) -> std::vec::IntoIter<Node<'a, 'input>> {
+    let (parents, name) = path.rsplit_once('/').unwrap_or(("", path));
+    let mut parent = node;
+    for step in parents.split('/').filter(|step| !step.is_empty()) {
+        parent = match child(parent, step) {
+            Some(child) => child,
+            None => return Vec::new().into_iter(),
+        };
+    }
+    parent
+        .children()
+        .filter(|child| child.is_element() && child.tag_name().name() == name)
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+// The trimmed text of the element at the end of a path, if it has any
+fn text(node: Node, path: &str) -> Option<String> {
+    let mut node = node;
+    for step in path.split('/') {
+        node = child(node, step)?;
+    }
+    let text: String = node
+        .descendants()
+        .filter(Node::is_text)
+        .filter_map(|text| text.text())
+        .collect();
+    non_empty(text.trim())
+}
+
+// List the child elements of `node` holding anything that are not `known`
+fn report(unmapped: &mut Vec<String>, path: &str, node: Node, known: &[&str]) {
+    for child in node.children().filter(Node::is_element) {
+        let name = child.tag_name().name();
+        let empty = child.descendants().all(|node| {
+            node.attributes().next().is_none()
+                && node.text().is_none_or(|text| text.trim().is_empty())
+        });
+        if known.contains(&name) || empty {
+            continue;
+        }
+        if path.is_empty() {
+            unmapped.push(name.to_string());
+        } else {
+            unmapped.push(format!("{}/{}", path, name));
+        }
+    }
+}
+
+fn join(items: &[String]) -> Option<String> {
+    non_empty(&items.join(", "))
+}
+
+fn split(text: Option<String>) -> Vec<String> {
+    text.iter()
+        .flat_map(|text| text.split(','))
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+// Builds an indented XML document one element at a time
+#[derive(Default)]
+struct Writer {
+    xml: String,
+    open: Vec<String>,
+}
+
+impl Writer {
+    fn indent(&mut self) {
+        for _ in 0..self.open.len() {
+            self.xml.push_str("  ");
+        }
+    }
+
+    fn tag(tag: &str, attributes: &[(&str, &str)]) -> String {
+        let mut result = String::from(tag);
+        for (name, value) in attributes {
+            result.push_str(&format!(" {}=\"{}\"", name, escape(value)));
+        }
+        result
+    }
+
+    fn start(&mut self, tag: &str, attributes: &[(&str, &str)]) {
+        self.indent();
+        self.xml
+            .push_str(&format!("<{}>\n", Self::tag(tag, attributes)));
+        self.open.push(tag.to_string());
+    }
+
+    fn end(&mut self) {
+        let tag = self.open.pop().expect("no element to end");
+        self.indent();
+        self.xml.push_str(&format!("</{}>\n", tag));
+    }
+
+    fn leaf(&mut self, tag: &str, text: &str) {
+        self.indent();
+        self.xml
+            .push_str(&format!("<{}>{}</{}>\n", tag, escape(text), tag));
+    }
+
+    fn empty(&mut self, tag: &str, attributes: &[(&str, &str)]) {
+        self.indent();
+        self.xml
+            .push_str(&format!("<{}/>\n", Self::tag(tag, attributes)));
+    }
+
+    fn finish(self) -> String {
+        String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n") + &self.xml
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Color, Direction, HorizontalAlignment, Theme, VerticalAlignment};
+use super::{stylesheet::stylesheet, Color, Direction, HorizontalAlignment, Theme, VerticalAlignment};
 
 // The actual internal structure that is directly 1:1 with the output code.
 // When the program finally inserts the user data into the resume, the
@@ -16,18 +16,31 @@ pub enum HTML {
 
     Row(Vec<Self>),
     Column(Vec<Self>),
+    // Columns of set widths, in twelfths of the row like Bootstrap's grid
+    Grid(Vec<(u8, Self)>),
 
     // Aligned(Box<Self>, HorizontalAlignment, VerticalAlignment),
     Text(String),
     Title(String),
     SectionTitle(String),
 
+    // Text that flows inline with its neighbours instead of starting a new paragraph
+    Span(String),
+    // A single paragraph built from inline pieces, like spans and bold or italic spans
+    Paragraph(Vec<Self>),
+
+    // Trusted markup that is inserted into the output without escaping.
+    // Never put user input in here!
+    Raw(String),
+
     // 1, 2, 3, ...
     OrderedList(Vec<Self>),
     // Dotted list
     UnorderedList(Vec<Self>),
 
     PercentBar(u32, String),
+    // A small inline label, like a technology used in a project
+    Badge(String),
 
     // A colored rectangle with border radius
     Rectangle(Box<Self>, u32, Color),
@@ -45,6 +58,12 @@ pub enum HTML {
     ColoredForeground(Box<Self>, Color),
     ColoredBackground(Box<Self>, Color),
 
+    // Microformat classes, like `h-card` or `p-name`, telling machines what
+    // the content inside is. Only webpages show them.
+    Microformat(Box<Self>, String),
+    // Machine-readable data about the page, as JSON-LD
+    JsonLd(String),
+
     FadeIn(Direction),
 }
 
@@ -55,6 +74,7 @@ impl From<String> for HTML {
 }
 
 impl HTML {
+    #[allow(clippy::self_named_constructors)]
     pub fn html(contents: Vec<Self>) -> Self {
         Self::Document(contents)
     }
@@ -73,6 +93,15 @@ impl HTML {
     pub fn text(text: impl ToString) -> Self {
         Self::Text(text.to_string())
     }
+    pub fn span(text: impl ToString) -> Self {
+        Self::Span(text.to_string())
+    }
+    pub fn raw(markup: impl ToString) -> Self {
+        Self::Raw(markup.to_string())
+    }
+    pub fn badge(label: impl ToString) -> Self {
+        Self::Badge(label.to_string())
+    }
     pub fn rect(content: Self, border_radius: u32, color: Color) -> Self {
         Self::Rectangle(Box::new(content), border_radius, color)
     }
@@ -97,6 +126,25 @@ impl HTML {
         Self::ColoredBackground(Box::new(content), color)
     }
 
+    pub fn microformat(content: Self, class: impl ToString) -> Self {
+        Self::Microformat(Box::new(content), class.to_string())
+    }
+    pub fn json_ld(data: impl ToString) -> Self {
+        Self::JsonLd(data.to_string())
+    }
+
+    /// Embed machine-readable data at the start of a document, like the
+    /// JSON-LD from `Resume::to_json_ld`. Anything else is left as it is.
+    pub fn with_json_ld(self, data: impl ToString) -> Self {
+        match self {
+            Self::Document(mut contents) => {
+                contents.insert(0, Self::json_ld(data));
+                Self::Document(contents)
+            }
+            other => other,
+        }
+    }
+
     pub fn row<T>(items: Vec<T>) -> Self
     where
         T: Into<HTML>,
@@ -104,6 +152,18 @@ impl HTML {
         Self::Row(items.into_iter().map(Into::into).collect())
     }
 
+    pub fn grid<T>(columns: Vec<(u8, T)>) -> Self
+    where
+        T: Into<HTML>,
+    {
+        Self::Grid(
+            columns
+                .into_iter()
+                .map(|(width, item)| (width, item.into()))
+                .collect(),
+        )
+    }
+
     pub fn col<T>(items: Vec<T>) -> Self
     where
         T: Into<HTML>,
@@ -132,21 +192,95 @@ impl HTML {
         Self::UnorderedList(items.into_iter().map(Into::into).collect())
     }
 
+    pub fn paragraph<T>(items: Vec<T>) -> Self
+    where
+        T: Into<HTML>,
+    {
+        Self::Paragraph(items.into_iter().map(Into::into).collect())
+    }
+
+    /// Compile to a single file that renders without a network connection.
+    /// Instead of linking Bootstrap and web fonts from CDNs, only the styles
+    /// needed by the elements in this tree are inlined, and the theme's
+    /// offline CSS is used so that fonts fall back to ones installed locally.
+    pub fn compile_offline(&self, theme: &dyn Theme) -> String {
+        match self {
+            Self::Document(contents) => Self::compile_document(
+                "",
+                format!("{}\n{}", stylesheet(self), theme.get_offline_document_css()),
+                contents,
+                theme,
+            ),
+            other => other.compile(theme),
+        }
+    }
+
+    // The skeleton shared by online and offline documents. `head` holds any
+    // external resources, and `css` the styles to inline before the colors.
+    fn compile_document(head: &str, css: String, contents: &[Self], theme: &dyn Theme) -> String {
+        format!("<!DOCTYPE html5><html><head><meta content=\"text/html;charset=utf-8\" http-equiv=\"Content-Type\"><meta content=\"utf-8\" http-equiv=\"encoding\">{}<style>{}\nbody, div {{ color: {}; background-color: {}; }}\n.progress-bar {{ background-color: {}; }}</style></head><body><br><br><br><br><br><br>{}<br><br><br><br><br><br></body></html>",
+            head,
+            css,
+            theme.get_color_hex(Color::DefaultForeground),
+            theme.get_color_hex(Color::DefaultBackground),
+            // Skill bars are filled with the same color in every format
+            theme.get_color_hex(Color::DefaultSectionTitle),
+            contents.iter()
+                .map(|i| i.compile(theme))
+                .collect::<Vec<String>>()
+                .join("\n"))
+    }
+
+    /// The elements directly inside this one
+    pub fn children(&self) -> Vec<&Self> {
+        match self {
+            Self::Document(items)
+            | Self::Container(items)
+            | Self::Row(items)
+            | Self::Column(items)
+            | Self::OrderedList(items)
+            | Self::UnorderedList(items)
+            | Self::Paragraph(items) => items.iter().collect(),
+            Self::Grid(columns) => columns.iter().map(|(_, item)| item).collect(),
+
+            Self::Aligned(content, _, _)
+            | Self::Rectangle(content, _, _)
+            | Self::Section(content)
+            | Self::Italics(content)
+            | Self::Bold(content)
+            | Self::Link(content, _)
+            | Self::ColoredForeground(content, _)
+            | Self::ColoredBackground(content, _)
+            | Self::Microformat(content, _) => vec![content],
+
+            Self::Text(_)
+            | Self::Title(_)
+            | Self::SectionTitle(_)
+            | Self::Span(_)
+            | Self::Raw(_)
+            | Self::PercentBar(_, _)
+            | Self::Badge(_)
+            | Self::JsonLd(_)
+            | Self::FadeIn(_) => vec![],
+        }
+    }
+
+    /// Does this element, or any element inside it, satisfy `predicate`?
+    pub fn contains(&self, predicate: &dyn Fn(&Self) -> bool) -> bool {
+        predicate(self) || self.children().iter().any(|child| child.contains(predicate))
+    }
+
     pub fn compile(&self, theme: &dyn Theme) -> String {
         match self {
-            Self::Document(contents) => {
-                format!("<!DOCTYPE html5><html><head><meta content=\"text/html;charset=utf-8\" http-equiv=\"Content-Type\"><meta content=\"utf-8\" http-equiv=\"encoding\"><link rel=\"stylesheet\" href=\"https://stackpath.bootstrapcdn.com/bootstrap/4.3.1/css/bootstrap.min.css\" integrity=\"sha384-ggOyR0iXCbMQv3Xipma34MD+dH/1fQ784/j6cY/iJTQUOhcWr7x9JvoRxT2MZw1T\" crossorigin=\"anonymous\"><script src=\"https://code.jquery.com/jquery-3.3.1.slim.min.js\" integrity=\"sha384-q8i/X+965DzO0rT7abK41JStQIAqVgRVzpbzo5smXKp4YfRvH+8abtTE1Pi6jizo\" crossorigin=\"anonymous\"></script><script src=\"https://cdnjs.cloudflare.com/ajax/libs/popper.js/1.14.7/umd/popper.min.js\" integrity=\"sha384-UO2eT0CpHqdSJQ6hJty5KVphtPhzWj9WO1clHTMGa3JDZwrnQq4sF86dIHNDz0W1\" crossorigin=\"anonymous\"></script><script src=\"https://stackpath.bootstrapcdn.com/bootstrap/4.3.1/js/bootstrap.min.js\" integrity=\"sha384-JjSmVgyd0p3pXB1rRibZUAYoIIy6OrQ6VrjIEaFf/nJGzIxFDsf4x0xIM+B07jRM\" crossorigin=\"anonymous\"></script><style>{}\nbody, div {{ color: {}; background-color: {}; }}</style></head><body><br><br><br><br><br><br>{}<br><br><br><br><br><br></body></html>",
+            Self::Document(contents) => Self::compile_document(
+                "<link rel=\"stylesheet\" href=\"https://stackpath.bootstrapcdn.com/bootstrap/4.3.1/css/bootstrap.min.css\" integrity=\"sha384-ggOyR0iXCbMQv3Xipma34MD+dH/1fQ784/j6cY/iJTQUOhcWr7x9JvoRxT2MZw1T\" crossorigin=\"anonymous\"><script src=\"https://code.jquery.com/jquery-3.3.1.slim.min.js\" integrity=\"sha384-q8i/X+965DzO0rT7abK41JStQIAqVgRVzpbzo5smXKp4YfRvH+8abtTE1Pi6jizo\" crossorigin=\"anonymous\"></script><script src=\"https://cdnjs.cloudflare.com/ajax/libs/popper.js/1.14.7/umd/popper.min.js\" integrity=\"sha384-UO2eT0CpHqdSJQ6hJty5KVphtPhzWj9WO1clHTMGa3JDZwrnQq4sF86dIHNDz0W1\" crossorigin=\"anonymous\"></script><script src=\"https://stackpath.bootstrapcdn.com/bootstrap/4.3.1/js/bootstrap.min.js\" integrity=\"sha384-JjSmVgyd0p3pXB1rRibZUAYoIIy6OrQ6VrjIEaFf/nJGzIxFDsf4x0xIM+B07jRM\" crossorigin=\"anonymous\"></script>",
                 theme.get_document_css(),
-                theme.get_color_hex(Color::DefaultForeground),
-                theme.get_color_hex(Color::DefaultBackground),
-                contents.iter()
-                    .map(|i| i.compile(theme))
-                    .collect::<Vec<String>>()
-                    .join("\n"))
-            }
+                contents,
+                theme,
+            ),
 
             Self::PercentBar(part, label) => {
-                format!("<div class=\"progress\"><div class=\"progress-bar\" role=\"progressbar\" style=\"width:{part}%\"  aria-valuenow=\"{part}\" aria-valuemin=\"{part}\" aria-valuemax=\"100\">{}</div></div>", label, part=part)
+                format!("<div class=\"progress\"><div class=\"progress-bar\" role=\"progressbar\" style=\"width:{part}%\"  aria-valuenow=\"{part}\" aria-valuemin=\"{part}\" aria-valuemax=\"100\">{}</div></div>", escape(label), part=part)
             }
 
             Self::Aligned(contents, hori, vert) => {
@@ -177,6 +311,21 @@ impl HTML {
                 )
             }
 
+            Self::Grid(columns) => {
+                format!(
+                    "<div class=\"row no-gutters\">{}</div>",
+                    columns
+                        .iter()
+                        .map(|(width, i)| format!(
+                            "<div class=\"col-{} no-gutters\">{}</div>",
+                            width,
+                            i.compile(theme)
+                        ))
+                        .collect::<Vec<String>>()
+                        .join("\n")
+                )
+            }
+
             Self::Column(items) => {
                 format!(
                     "<div class=\"col no-gutters\">{}</div>",
@@ -233,15 +382,35 @@ impl HTML {
                 )
             }
 
-            Self::Title(title) => format!("<h1>{}</h1>", title),
-            Self::SectionTitle(title) => format!("<h4>{}</h4>", title),
+            Self::Title(title) => format!("<h1>{}</h1>", escape(title)),
+            Self::SectionTitle(title) => format!("<h4>{}</h4>", escape(title)),
             Self::Section(contents) => theme.compile_section_html(contents.compile(theme)),
-            Self::Text(text) => format!("<p>{}</p>", text),
+            Self::Text(text) => format!("<p>{}</p>", escape(text)),
+            Self::Span(text) => escape(text),
+            Self::Badge(label) => format!(
+                "<span class=\"badge\" style=\"color:{}; background-color:{}\">{}</span>",
+                theme.get_color_hex(Color::DefaultBackground),
+                theme.get_color_hex(Color::DefaultSubtitle),
+                escape(label)
+            ),
+            Self::Paragraph(items) => format!(
+                "<p>{}</p>",
+                items
+                    .iter()
+                    .map(|i| i.compile(theme))
+                    .collect::<Vec<String>>()
+                    .join("")
+            ),
+            Self::Raw(markup) => markup.clone(),
 
             Self::Italics(x) => format!("<i>{}</i>", x.compile(theme)),
             Self::Bold(x) => format!("<b>{}</b>", x.compile(theme)),
             Self::Link(content, link) => {
-                format!("<a href=\"{}\">{}</a>", link, content.compile(theme))
+                format!(
+                    "<a href=\"{}\">{}</a>",
+                    escape(&safe_url(link)),
+                    content.compile(theme)
+                )
             }
 
             Self::ColoredForeground(contents, color) => {
@@ -260,7 +429,73 @@ impl HTML {
                 )
             }
 
+            Self::Microformat(contents, class) => with_class(contents.compile(theme), class),
+            // Markup inside a script can end it early, or keep it from
+            // ending at all, so `<`, `>` and `&` are written as JSON escapes
+            Self::JsonLd(data) => format!(
+                "<script type=\"application/ld+json\">{}</script>",
+                data.replace('&', "\\u0026")
+                    .replace('<', "\\u003c")
+                    .replace('>', "\\u003e")
+            ),
+
             Self::FadeIn(_) => unimplemented!(),
         }
     }
 }
+
+// Add a class to the outermost element of compiled markup, so that marking
+// content with a microformat does not change how the page is laid out.
+// Bare text is wrapped in a span to hold the class.
+fn with_class(markup: String, class: &str) -> String {
+    let class = escape(class);
+    let tag_end = match markup.find('>') {
+        Some(end) if markup.starts_with('<') => end,
+        _ => return format!("<span class=\"{}\">{}</span>", class, markup),
+    };
+    match markup[..tag_end].find(" class=\"") {
+        Some(start) => {
+            let at = start + " class=\"".len();
+            format!("{}{} {}", &markup[..at], class, &markup[at..])
+        }
+        None => {
+            let at = markup[..tag_end]
+                .find(|c: char| c.is_whitespace() || c == '/')
+                .unwrap_or(tag_end);
+            format!("{} class=\"{}\"{}", &markup[..at], class, &markup[at..])
+        }
+    }
+}
+
+/// Escape text so that it can be safely placed inside an element or a quoted attribute
+pub(crate) fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            ch => result.push(ch),
+        }
+    }
+    result
+}
+
+// Links that would run code when clicked are replaced with a dead link.
+// Browsers ignore whitespace and control characters inside the scheme,
+// so those are stripped before checking it.
+fn safe_url(link: &str) -> String {
+    let scheme = link
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .take_while(|c| *c != ':')
+        .collect::<String>()
+        .to_lowercase();
+    if link.contains(':') && ["javascript", "vbscript", "data"].contains(&scheme.as_str()) {
+        String::from("#")
+    } else {
+        link.to_string()
+    }
+}
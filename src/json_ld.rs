@@ -0,0 +1,83 @@
+use super::Resume;
+use serde_json::{json, Map, Value};
+
+// A schema.org Person (https://schema.org/Person) describing whose resume a
+// page is, for search engines and other readers that understand JSON-LD
+
+impl Resume {
+    /// Describe the person behind this resume as a schema.org `Person` in
+    /// JSON-LD: their name and job title, the schools they went to, where
+    /// they work now, and their contact details and profiles.
+    pub fn to_json_ld(&self) -> String {
+        let mut person = Map::new();
+        person.insert(String::from("@context"), json!("https://schema.org"));
+        person.insert(String::from("@type"), json!("Person"));
+        person.insert(
+            String::from("name"),
+            json!(format!("{} {}", self.first_name, self.last_name).trim()),
+        );
+        person.insert(String::from("givenName"), json!(self.first_name));
+        person.insert(String::from("familyName"), json!(self.last_name));
+        if !self.profession.is_empty() {
+            person.insert(String::from("jobTitle"), json!(self.profession));
+        }
+        if !self.description.is_empty() {
+            person.insert(String::from("description"), json!(self.description));
+        }
+
+        let schools = organizations(
+            "EducationalOrganization",
+            self.education.iter().map(|education| &education.school),
+        );
+        if !schools.is_empty() {
+            person.insert(String::from("alumniOf"), Value::Array(schools));
+        }
+        // Only jobs that have not ended yet
+        let employers = organizations(
+            "Organization",
+            self.work_experience
+                .iter()
+                .filter(|job| !job.end.has_passed())
+                .map(|job| &job.company),
+        );
+        if !employers.is_empty() {
+            person.insert(String::from("worksFor"), Value::Array(employers));
+        }
+
+        let mut profiles = Vec::new();
+        for (label, text, url) in self.contact_info.links() {
+            match label {
+                "Email" => {
+                    person.insert(String::from("email"), json!(text.trim()));
+                }
+                "Phone" => {
+                    person.insert(String::from("telephone"), json!(text.trim()));
+                }
+                "Website" => {
+                    person.insert(String::from("url"), json!(url));
+                }
+                _ => profiles.push(json!(url)),
+            }
+        }
+        if !profiles.is_empty() {
+            person.insert(String::from("sameAs"), Value::Array(profiles));
+        }
+
+        serde_json::to_string_pretty(&person).expect("a JSON map always serializes")
+    }
+}
+
+// Each distinct, non-empty name as an organization of the given type
+fn organizations<'a>(kind: &str, names: impl Iterator<Item = &'a String>) -> Vec<Value> {
+    let mut seen: Vec<&str> = Vec::new();
+    names
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .filter(|name| {
+            let new = !seen.contains(name);
+            seen.push(name);
+            new
+        })
+        .map(|name| json!({ "@type": kind, "name": name }))
+        .collect()
+}
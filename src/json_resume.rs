@@ -0,0 +1,652 @@
+use super::{
+    document::parse,
+    resume::{non_empty, profile_name, profile_url},
+    Award, Certification, ContactInfo, Conversion, Date, Degree, DocumentError, Education, Format,
+    Layout, Proficiency, Project, Publication, Resume, Work,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+// The parts of the JSON Resume schema (https://jsonresume.org/schema) that
+// have a place in a `Resume`. Every other field is collected in `other`, so
+// that it can be reported as unmapped instead of silently dropped.
+
+#[derive(Default, Serialize, Deserialize)]
+struct JsonResume {
+    #[serde(rename = "$schema", default, skip_serializing_if = "Option::is_none")]
+    schema: Option<String>,
+    #[serde(default)]
+    basics: Basics,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    work: Vec<JsonWork>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    education: Vec<JsonEducation>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    skills: Vec<JsonSkill>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    projects: Vec<JsonProject>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    certificates: Vec<JsonCertificate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    awards: Vec<JsonAward>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    publications: Vec<JsonPublication>,
+    #[serde(flatten)]
+    other: BTreeMap<String, Value>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Basics {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    phone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    profiles: Vec<Profile>,
+    #[serde(flatten)]
+    other: BTreeMap<String, Value>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Profile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    network: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(flatten)]
+    other: BTreeMap<String, Value>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonWork {
+    // Older versions of the schema called the employer `company`
+    #[serde(default, alias = "company", skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    highlights: Vec<String>,
+    #[serde(flatten)]
+    other: BTreeMap<String, Value>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonEducation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    institution: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    area: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    study_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end_date: Option<String>,
+    #[serde(flatten)]
+    other: BTreeMap<String, Value>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct JsonSkill {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    level: Option<String>,
+    #[serde(flatten)]
+    other: BTreeMap<String, Value>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonProject {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    roles: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    keywords: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    highlights: Vec<String>,
+    #[serde(flatten)]
+    other: BTreeMap<String, Value>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct JsonCertificate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    issuer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(flatten)]
+    other: BTreeMap<String, Value>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct JsonAward {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    awarder: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+    #[serde(flatten)]
+    other: BTreeMap<String, Value>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonPublication {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    publisher: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    release_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(flatten)]
+    other: BTreeMap<String, Value>,
+}
+
+const SCHEMA: &str =
+    "https://raw.githubusercontent.com/jsonresume/resume-schema/v1.0.0/schema.json";
+const DOI_RESOLVER: &str = "https://doi.org/";
+
+impl Resume {
+    /// Read a resume written in the JSON Resume schema used by
+    /// jsonresume.org. Fields without a place in a `Resume`, such as
+    /// `basics.location` or `volunteer`, are listed in the conversion.
+    pub fn from_json_resume(text: &str) -> Result<Conversion<Self>, DocumentError> {
+        let json: JsonResume = parse(text, Format::Json)?;
+        let mut unmapped = Vec::new();
+        report(&mut unmapped, "", &json.other);
+
+        let basics = json.basics;
+        report(&mut unmapped, "basics", &basics.other);
+        let name = basics.name.unwrap_or_default();
+        let (first_name, last_name) = match name.trim().split_once(char::is_whitespace) {
+            Some((first, last)) => (first.to_string(), last.trim().to_string()),
+            None => (name.trim().to_string(), String::new()),
+        };
+        let mut contact_info = ContactInfo {
+            email: basics.email.filter(|email| !email.trim().is_empty()),
+            phone: basics.phone.filter(|phone| !phone.trim().is_empty()),
+            website: basics.url.filter(|url| !url.trim().is_empty()),
+            github: None,
+            linkedin: None,
+        };
+        for (i, profile) in basics.profiles.into_iter().enumerate() {
+            let path = format!("basics.profiles[{}]", i);
+            let network = profile.network.unwrap_or_default().to_lowercase();
+            let slot = match network.as_str() {
+                "github" => &mut contact_info.github,
+                "linkedin" => &mut contact_info.linkedin,
+                _ => {
+                    unmapped.push(path);
+                    continue;
+                }
+            };
+            // Either the username or the whole URL will do for a profile
+            let username = profile.username.filter(|name| !name.trim().is_empty());
+            match username
+                .or(profile.url)
+                .filter(|url| !url.trim().is_empty())
+            {
+                Some(profile) if slot.is_none() => *slot = Some(profile),
+                _ => unmapped.push(path.clone()),
+            }
+            report(&mut unmapped, &path, &profile.other);
+        }
+
+        let mut work_experience = Vec::new();
+        for (i, job) in json.work.into_iter().enumerate() {
+            let path = format!("work[{}]", i);
+            report(&mut unmapped, &path, &job.other);
+            let start = match date(&mut unmapped, &path, "startDate", job.start_date) {
+                Some(start) => start,
+                // A job can't be shown without knowing when it started
+                None => {
+                    unmapped.push(path);
+                    continue;
+                }
+            };
+            work_experience.push(Work {
+                start,
+                end: date(&mut unmapped, &path, "endDate", job.end_date).unwrap_or(Date::Present),
+                position: job.position.unwrap_or_default(),
+                company: job.name.unwrap_or_default(),
+                description: job.summary.unwrap_or_default(),
+                highlights: job.highlights,
+            });
+        }
+
+        let mut education = Vec::new();
+        for (i, school) in json.education.into_iter().enumerate() {
+            let path = format!("education[{}]", i);
+            report(&mut unmapped, &path, &school.other);
+            let start = match date(&mut unmapped, &path, "startDate", school.start_date) {
+                Some(start) => start,
+                None => {
+                    unmapped.push(path);
+                    continue;
+                }
+            };
+            let degree = school.study_type.filter(|study| !study.trim().is_empty());
+            education.push(Education {
+                start,
+                end: date(&mut unmapped, &path, "endDate", school.end_date)
+                    .unwrap_or(Date::Present),
+                school: school.institution.unwrap_or_default(),
+                field: school.area.filter(|area| !area.trim().is_empty()),
+                degree: degree.and_then(|study| {
+                    let degree = degree_from_study_type(&study);
+                    if degree.is_none() {
+                        unmapped.push(format!("{}.studyType", path));
+                    }
+                    degree
+                }),
+            });
+        }
+
+        let mut skills = Vec::new();
+        for (i, skill) in json.skills.into_iter().enumerate() {
+            let path = format!("skills[{}]", i);
+            report(&mut unmapped, &path, &skill.other);
+            let level = skill.level.filter(|level| !level.trim().is_empty());
+            let level = level.and_then(|level| {
+                let proficiency = proficiency_from_level(&level);
+                if proficiency.is_none() {
+                    unmapped.push(format!("{}.level", path));
+                }
+                proficiency
+            });
+            match skill.name {
+                Some(name) if !name.trim().is_empty() => skills.push((name, level)),
+                _ => unmapped.push(path),
+            }
+        }
+
+        let mut projects = Vec::new();
+        for (i, project) in json.projects.into_iter().enumerate() {
+            let path = format!("projects[{}]", i);
+            report(&mut unmapped, &path, &project.other);
+            projects.push(Project {
+                name: project.name.unwrap_or_default(),
+                url: project.url,
+                repository: None,
+                role: Some(project.roles.join(", ")).filter(|roles| !roles.is_empty()),
+                start: date(&mut unmapped, &path, "startDate", project.start_date),
+                end: date(&mut unmapped, &path, "endDate", project.end_date),
+                technologies: project.keywords,
+                highlights: project.highlights,
+            });
+        }
+
+        let mut certifications = Vec::new();
+        for (i, certificate) in json.certificates.into_iter().enumerate() {
+            let path = format!("certificates[{}]", i);
+            report(&mut unmapped, &path, &certificate.other);
+            certifications.push(Certification {
+                name: certificate.name.unwrap_or_default(),
+                issuer: certificate.issuer.unwrap_or_default(),
+                credential_id: None,
+                issued: date(&mut unmapped, &path, "date", certificate.date),
+                expires: None,
+                url: certificate.url,
+            });
+        }
+
+        let mut awards = Vec::new();
+        for (i, award) in json.awards.into_iter().enumerate() {
+            let path = format!("awards[{}]", i);
+            report(&mut unmapped, &path, &award.other);
+            awards.push(Award {
+                title: award.title.unwrap_or_default(),
+                issuer: award.awarder,
+                date: date(&mut unmapped, &path, "date", award.date),
+                summary: award.summary,
+            });
+        }
+
+        let mut publications = Vec::new();
+        for (i, publication) in json.publications.into_iter().enumerate() {
+            let path = format!("publications[{}]", i);
+            report(&mut unmapped, &path, &publication.other);
+            // A DOI is written as its resolver link, so read it back as one
+            let (doi, url) = match publication.url {
+                Some(url) if url.starts_with(DOI_RESOLVER) => {
+                    (Some(url[DOI_RESOLVER.len()..].to_string()), None)
+                }
+                url => (None, url),
+            };
+            publications.push(Publication {
+                title: publication.name.unwrap_or_default(),
+                authors: Vec::new(),
+                venue: publication.publisher,
+                year: date(
+                    &mut unmapped,
+                    &path,
+                    "releaseDate",
+                    publication.release_date,
+                )
+                .map(|date| date.year()),
+                doi,
+                url,
+            });
+        }
+
+        Ok(Conversion {
+            value: Resume {
+                first_name,
+                last_name,
+                description: basics.summary.unwrap_or_default(),
+                contact_info,
+                profession: basics.label.unwrap_or_default(),
+                skills,
+                education,
+                work_experience,
+                projects,
+                certifications,
+                awards,
+                publications,
+                custom_sections: Vec::new(),
+                layout: Layout::default(),
+            },
+            unmapped,
+        })
+    }
+
+    /// Write this resume in the JSON Resume schema used by jsonresume.org.
+    /// Details the schema has no field for, such as custom sections or
+    /// credential IDs, are listed in the conversion.
+    pub fn to_json_resume(&self) -> Result<Conversion<String>, DocumentError> {
+        let mut unmapped = Vec::new();
+        let contact = &self.contact_info;
+
+        let mut profiles = Vec::new();
+        if let Some(github) = &contact.github {
+            profiles.push(Profile {
+                network: Some(String::from("GitHub")),
+                username: Some(profile_name(github, "github.com").to_string()),
+                url: Some(profile_url(github, "github.com", "https://github.com/")),
+                other: BTreeMap::new(),
+            });
+        }
+        if let Some(linkedin) = &contact.linkedin {
+            profiles.push(Profile {
+                network: Some(String::from("LinkedIn")),
+                username: Some(profile_name(linkedin, "linkedin.com/in").to_string()),
+                url: Some(profile_url(
+                    linkedin,
+                    "linkedin.com",
+                    "https://www.linkedin.com/in/",
+                )),
+                other: BTreeMap::new(),
+            });
+        }
+
+        for (i, certification) in self.certifications.iter().enumerate() {
+            if certification.credential_id.is_some() {
+                unmapped.push(format!("certifications[{}].credential_id", i));
+            }
+            if certification.expires.is_some() {
+                unmapped.push(format!("certifications[{}].expires", i));
+            }
+        }
+        for (i, project) in self.projects.iter().enumerate() {
+            if project.repository.is_some() {
+                unmapped.push(format!("projects[{}].repository", i));
+            }
+        }
+        for (i, publication) in self.publications.iter().enumerate() {
+            if !publication.authors.is_empty() {
+                unmapped.push(format!("publications[{}].authors", i));
+            }
+            // The DOI is kept as the link when there is no other
+            if publication.doi.is_some() && publication.url.is_some() {
+                unmapped.push(format!("publications[{}].doi", i));
+            }
+        }
+        for (i, _) in self.custom_sections.iter().enumerate() {
+            unmapped.push(format!("custom_sections[{}]", i));
+        }
+        if self.layout != Layout::default() {
+            unmapped.push(String::from("layout"));
+        }
+
+        let json = JsonResume {
+            schema: Some(String::from(SCHEMA)),
+            basics: Basics {
+                name: Some(
+                    format!("{} {}", self.first_name, self.last_name)
+                        .trim()
+                        .to_string(),
+                ),
+                label: non_empty(&self.profession),
+                email: contact.email.clone(),
+                phone: contact.phone.clone(),
+                url: contact.website.clone(),
+                summary: non_empty(&self.description),
+                profiles,
+                other: BTreeMap::new(),
+            },
+            work: self
+                .work_experience
+                .iter()
+                .map(|job| JsonWork {
+                    name: non_empty(&job.company),
+                    position: non_empty(&job.position),
+                    start_date: date_string(job.start),
+                    end_date: date_string(job.end),
+                    summary: non_empty(&job.description),
+                    highlights: job.highlights.clone(),
+                    other: BTreeMap::new(),
+                })
+                .collect(),
+            education: self
+                .education
+                .iter()
+                .map(|school| JsonEducation {
+                    institution: non_empty(&school.school),
+                    area: school.field.clone(),
+                    study_type: school.degree.map(study_type),
+                    start_date: date_string(school.start),
+                    end_date: date_string(school.end),
+                    other: BTreeMap::new(),
+                })
+                .collect(),
+            skills: self
+                .skills
+                .iter()
+                .map(|(name, level)| JsonSkill {
+                    name: Some(name.clone()),
+                    level: level.map(level_name),
+                    other: BTreeMap::new(),
+                })
+                .collect(),
+            projects: self
+                .projects
+                .iter()
+                .map(|project| JsonProject {
+                    name: non_empty(&project.name),
+                    url: project.url.clone(),
+                    roles: project.role.iter().cloned().collect(),
+                    start_date: project.start.and_then(date_string),
+                    end_date: project.end.and_then(date_string),
+                    keywords: project.technologies.clone(),
+                    highlights: project.highlights.clone(),
+                    other: BTreeMap::new(),
+                })
+                .collect(),
+            certificates: self
+                .certifications
+                .iter()
+                .map(|certification| JsonCertificate {
+                    name: non_empty(&certification.name),
+                    issuer: non_empty(&certification.issuer),
+                    date: certification.issued.and_then(date_string),
+                    url: certification.url.clone(),
+                    other: BTreeMap::new(),
+                })
+                .collect(),
+            awards: self
+                .awards
+                .iter()
+                .map(|award| JsonAward {
+                    title: non_empty(&award.title),
+                    awarder: award.issuer.clone(),
+                    date: award.date.and_then(date_string),
+                    summary: award.summary.clone(),
+                    other: BTreeMap::new(),
+                })
+                .collect(),
+            publications: self
+                .publications
+                .iter()
+                .map(|publication| JsonPublication {
+                    name: non_empty(&publication.title),
+                    publisher: publication.venue.clone(),
+                    release_date: publication.year.map(|year| year.to_string()),
+                    url: publication.url.clone().or_else(|| publication.link()),
+                    other: BTreeMap::new(),
+                })
+                .collect(),
+            other: BTreeMap::new(),
+        };
+
+        Ok(Conversion {
+            value: serde_json::to_string_pretty(&json)
+                .map_err(|e| DocumentError::Serialize(e.to_string()))?,
+            unmapped,
+        })
+    }
+}
+
+// List the fields in `other` that hold anything, as fields of `path`
+fn report(unmapped: &mut Vec<String>, path: &str, other: &BTreeMap<String, Value>) {
+    for (key, value) in other {
+        let empty = match value {
+            Value::Null => true,
+            Value::String(text) => text.trim().is_empty(),
+            Value::Array(items) => items.is_empty(),
+            Value::Object(fields) => fields.is_empty(),
+            _ => false,
+        };
+        if !empty && path.is_empty() {
+            unmapped.push(key.clone());
+        } else if !empty {
+            unmapped.push(format!("{}.{}", path, key));
+        }
+    }
+}
+
+// Read an ISO 8601 date like `2021-03-15`, `2021-03` or `2021`. Days are
+// dropped, since resumes only show months.
+fn date(unmapped: &mut Vec<String>, path: &str, field: &str, text: Option<String>) -> Option<Date> {
+    let text = text.filter(|text| !text.trim().is_empty())?;
+    let month = text.trim().get(..7).filter(|_| text.trim().len() == 10);
+    match month.unwrap_or_else(|| text.trim()).parse() {
+        Ok(date) => Some(date),
+        Err(_) => {
+            unmapped.push(format!("{}.{}", path, field));
+            None
+        }
+    }
+}
+
+// Dates that are still going on are written by leaving them out
+fn date_string(date: Date) -> Option<String> {
+    match date {
+        Date::Year(year) => Some(year.to_string()),
+        Date::Month(year, month) => Some(format!("{}-{:02}", year, month)),
+        Date::Present => None,
+    }
+}
+
+fn degree_from_study_type(study: &str) -> Option<Degree> {
+    let study = study.to_lowercase();
+    // Dotted abbreviations are read as one word, so that `M.B.A.` is `mba`
+    // rather than the letters of three different degrees
+    let words: Vec<String> = study
+        .split(|c: char| !c.is_alphanumeric() && c != '.')
+        .map(|word| word.replace('.', ""))
+        .filter(|word| !word.is_empty())
+        .collect();
+    let has = |names: &[&str]| words.iter().any(|word| names.contains(&word.as_str()));
+    if study.contains("high school") || has(&["ged", "diploma"]) {
+        Some(Degree::HighSchoolDiploma)
+    } else if study.contains("associate") || has(&["aa", "as", "aas"]) {
+        Some(Degree::Associates)
+    } else if study.contains("bachelor") || has(&["ba", "bs", "bsc", "beng"]) {
+        Some(Degree::Bachelors)
+    } else if study.contains("master") || has(&["ma", "ms", "msc", "meng", "mba"]) {
+        Some(Degree::Masters)
+    } else if study.contains("doctor") || has(&["phd", "dphil"]) {
+        Some(Degree::PhD)
+    } else {
+        None
+    }
+}
+
+fn study_type(degree: Degree) -> String {
+    String::from(match degree {
+        Degree::HighSchoolDiploma => "High School Diploma",
+        Degree::Associates => "Associate",
+        Degree::Bachelors => "Bachelor",
+        Degree::Masters => "Master",
+        Degree::PhD => "PhD",
+    })
+}
+
+// JSON Resume levels are free text, so common words for each level are understood
+fn proficiency_from_level(level: &str) -> Option<Proficiency> {
+    match level.trim().to_lowercase().as_str() {
+        "none" => Some(Proficiency::None),
+        "beginner" | "novice" | "basic" | "elementary" | "barely" => Some(Proficiency::Barely),
+        "intermediate" | "familiar" | "competent" | "working" | "some" => Some(Proficiency::Some),
+        "advanced" | "proficient" | "fluent" | "strong" => Some(Proficiency::Strong),
+        "expert" | "master" | "native" => Some(Proficiency::Expert),
+        _ => None,
+    }
+}
+
+fn level_name(level: Proficiency) -> String {
+    String::from(match level {
+        Proficiency::None => "None",
+        Proficiency::Barely => "Beginner",
+        Proficiency::Some => "Intermediate",
+        Proficiency::Strong => "Advanced",
+        Proficiency::Expert => "Expert",
+    })
+}
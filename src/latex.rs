@@ -0,0 +1,293 @@
+use super::{resume::profile_name, Color, Proficiency, Resume, SectionId, Theme};
+
+// The width of a full skill bar, in ems
+const SKILL_BAR_WIDTH: u32 = 6;
+
+impl Resume {
+    /// Write this resume as a LaTeX document using the moderncv class.
+    /// Every theme color is defined with `\definecolor` under its `Color`
+    /// name, so the document can be restyled without touching the content.
+    pub fn to_latex(&self, theme: &dyn Theme) -> String {
+        let mut tex = String::from(
+            "\\documentclass[11pt,a4paper,sans]{moderncv}\n\
+             \\moderncvstyle{classic}\n\
+             \\moderncvcolor{blue}\n\
+             \\usepackage[utf8]{inputenc}\n\
+             \\usepackage[T1]{fontenc}\n\
+             \\usepackage[scale=0.8]{geometry}\n\n",
+        );
+
+        for color in Color::all().iter() {
+            let (r, g, b) = theme.get_color_rgb(*color);
+            tex += &format!("\\definecolor{{{:?}}}{{RGB}}{{{},{},{}}}\n", color, r, g, b);
+        }
+        // moderncv draws titles and rules with color1 and dates with color2
+        tex += "\\colorlet{color0}{DefaultForeground}\n\
+                \\colorlet{color1}{DefaultSectionTitle}\n\
+                \\colorlet{color2}{DefaultSubtitle}\n\
+                \\pagecolor{DefaultBackground}\n\n";
+
+        // \skillbar{filled width}{empty width}{label}
+        tex += r"\newcommand{\skillbar}[3]{\textcolor{DefaultSectionTitle}{\rule{#1em}{0.7em}}\textcolor{Grey}{\rule{#2em}{0.7em}}\hspace{0.7em}#3}";
+        tex += "\n\n";
+
+        tex += &format!(
+            "\\name{{{}}}{{{}}}\n",
+            escape(&self.first_name),
+            escape(&self.last_name)
+        );
+        if !self.profession.is_empty() {
+            tex += &format!("\\title{{{}}}\n", escape(&self.profession));
+        }
+
+        let contact = &self.contact_info;
+        if let Some(email) = &contact.email {
+            tex += &format!("\\email{{{}}}\n", escape(email));
+        }
+        if let Some(phone) = &contact.phone {
+            tex += &format!("\\phone[mobile]{{{}}}\n", escape(phone));
+        }
+        // moderncv shows these arguments as they are, and builds the links
+        // itself by adding `http://` or the profile's address in front
+        if let Some(website) = &contact.website {
+            let website = website.trim();
+            let address = website
+                .split_once("://")
+                .map(|(_, rest)| rest)
+                .unwrap_or(website);
+            tex += &format!("\\homepage{{{}}}\n", escape(address));
+        }
+        if let Some(github) = &contact.github {
+            let name = profile_name(github, "github.com");
+            tex += &format!("\\social[github]{{{}}}\n", escape(name));
+        }
+        if let Some(linkedin) = &contact.linkedin {
+            let name = profile_name(linkedin, "linkedin.com/in");
+            tex += &format!("\\social[linkedin]{{{}}}\n", escape(name));
+        }
+        if !self.description.is_empty() {
+            tex += &format!("\\quote{{{}}}\n", escape(&self.description));
+        }
+
+        tex += "\n\\begin{document}\n\\color{DefaultForeground}\n\\makecvtitle\n";
+
+        for id in self.visible_sections() {
+            let section = self.latex_section(&id);
+            if !section.is_empty() {
+                tex += &format!("\n\\section{{{}}}\n{}", escape(id.title()), section);
+            }
+        }
+
+        tex + "\n\\end{document}\n"
+    }
+
+    // The entries of a section, without its heading
+    fn latex_section(&self, id: &SectionId) -> String {
+        let mut tex = String::new();
+        match id {
+            SectionId::Skills => {
+                for (skill, level) in &self.skills {
+                    tex += &match level {
+                        Some(level) => {
+                            format!("\\cvitem{{{}}}{{{}}}\n", escape(skill), skill_bar(*level))
+                        }
+                        None => format!("\\cvitem{{{}}}{{}}\n", escape(skill)),
+                    };
+                }
+            }
+            SectionId::Education => {
+                for e in &self.education {
+                    let (title, field) = match (&e.degree, &e.field) {
+                        (Some(degree), field) => (degree.to_string(), field.clone()),
+                        (None, Some(field)) => (field.clone(), None),
+                        (None, None) => (String::from("Attended"), None),
+                    };
+                    tex += &format!(
+                        "\\cventry{{{}--{}}}{{{}}}{{{}}}{{{}}}{{}}{{}}\n",
+                        e.start,
+                        e.end,
+                        escape(&title),
+                        escape(&e.school),
+                        escape(&field.unwrap_or_default())
+                    );
+                }
+            }
+            SectionId::Experience => {
+                for j in &self.work_experience {
+                    let mut description = escape(&j.description);
+                    if !j.highlights.is_empty() {
+                        description += "\\begin{itemize}";
+                        for highlight in &j.highlights {
+                            description += &format!("\\item {}", escape(highlight));
+                        }
+                        description += "\\end{itemize}";
+                    }
+                    tex += &format!(
+                        "\\cventry{{{}--{}}}{{{}}}{{{}}}{{}}{{}}{{{}}}\n",
+                        j.start,
+                        j.end,
+                        escape(&j.position),
+                        escape(&j.company),
+                        description
+                    );
+                }
+            }
+            SectionId::Projects => {
+                for p in &self.projects {
+                    let name = match &p.url {
+                        Some(url) => {
+                            format!("\\href{{{}}}{{{}}}", escape_url(url), escape(&p.name))
+                        }
+                        None => escape(&p.name),
+                    };
+                    let mut description = String::new();
+                    if let Some(repository) = &p.repository {
+                        description += &format!(
+                            "Source: \\href{{{}}}{{{}}}",
+                            escape_url(repository),
+                            escape(repository)
+                        );
+                    }
+                    if !p.highlights.is_empty() {
+                        description += "\\begin{itemize}";
+                        for highlight in &p.highlights {
+                            description += &format!("\\item {}", escape(highlight));
+                        }
+                        description += "\\end{itemize}";
+                    }
+                    tex += &format!(
+                        "\\cventry{{{}}}{{{}}}{{{}}}{{{}}}{{}}{{{}}}\n",
+                        escape(&p.dates().unwrap_or_default()),
+                        name,
+                        escape(p.role.as_deref().unwrap_or_default()),
+                        escape(&p.technologies.join(", ")),
+                        description
+                    );
+                }
+            }
+            SectionId::Certifications => {
+                for c in &self.certifications {
+                    let name = match &c.url {
+                        Some(url) => {
+                            format!("\\href{{{}}}{{{}}}", escape_url(url), escape(&c.name))
+                        }
+                        None => escape(&c.name),
+                    };
+                    let id = c
+                        .credential_id
+                        .as_ref()
+                        .map(|id| format!("Credential ID {}", escape(id)))
+                        .unwrap_or_default();
+                    let validity = c.validity().map(|v| escape(&v)).unwrap_or_default();
+                    tex += &format!(
+                        "\\cventry{{}}{{{}}}{{{}}}{{{}}}{{}}{{{}}}\n",
+                        name,
+                        escape(&c.issuer),
+                        id,
+                        if c.is_expired() {
+                            format!("\\textbf{{{}}}", validity)
+                        } else {
+                            validity
+                        }
+                    );
+                }
+            }
+            SectionId::Awards => {
+                for a in &self.awards {
+                    tex += &format!(
+                        "\\cventry{{{}}}{{{}}}{{{}}}{{}}{{}}{{{}}}\n",
+                        a.date.map(|date| date.to_string()).unwrap_or_default(),
+                        escape(&a.title),
+                        escape(a.issuer.as_deref().unwrap_or_default()),
+                        escape(a.summary.as_deref().unwrap_or_default())
+                    );
+                }
+            }
+            SectionId::Publications => {
+                for p in &self.publications {
+                    let mut citation = String::new();
+                    if !p.authors.is_empty() {
+                        citation += &format!("{}. ", escape(&p.authors.join(", ")));
+                    }
+                    citation += &match p.link() {
+                        Some(link) => {
+                            format!("\\href{{{}}}{{{}}}.", escape_url(&link), escape(&p.title))
+                        }
+                        None => format!("{}.", escape(&p.title)),
+                    };
+                    if let Some(venue) = &p.venue {
+                        citation += &format!(" \\emph{{{}}}.", escape(venue));
+                    }
+                    tex += &format!(
+                        "\\cvitem{{{}}}{{{}}}\n",
+                        p.year.map(|year| year.to_string()).unwrap_or_default(),
+                        citation
+                    );
+                }
+            }
+            SectionId::Custom(_) => {
+                for entry in self.custom_entries(id) {
+                    let mut description = String::new();
+                    if !entry.bullets.is_empty() {
+                        description += "\\begin{itemize}";
+                        for bullet in &entry.bullets {
+                            description += &format!("\\item {}", escape(bullet));
+                        }
+                        description += "\\end{itemize}";
+                    }
+                    tex += &format!(
+                        "\\cventry{{{}}}{{{}}}{{{}}}{{{}}}{{}}{{{}}}\n",
+                        escape(&entry.dates().unwrap_or_default()),
+                        escape(&entry.heading),
+                        escape(entry.subheading.as_deref().unwrap_or_default()),
+                        escape(entry.location.as_deref().unwrap_or_default()),
+                        description
+                    );
+                }
+            }
+        }
+        tex
+    }
+}
+
+fn skill_bar(level: Proficiency) -> String {
+    let filled = SKILL_BAR_WIDTH * u32::from(level) / u32::from(Proficiency::Expert);
+    format!(
+        "\\skillbar{{{}}}{{{}}}{{{}}}",
+        filled,
+        SKILL_BAR_WIDTH - filled,
+        level
+    )
+}
+
+// hyperref reads URLs almost verbatim, but `%` and `#` must still be escaped
+fn escape_url(url: &str) -> String {
+    url.chars()
+        .filter(|c| !"\\{}".contains(*c))
+        .fold(String::new(), |mut result, ch| {
+            if ch == '%' || ch == '#' {
+                result.push('\\');
+            }
+            result.push(ch);
+            result
+        })
+}
+
+/// Escape the characters that have a special meaning in LaTeX
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\\' => result.push_str("\\textbackslash{}"),
+            '~' => result.push_str("\\textasciitilde{}"),
+            '^' => result.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                result.push('\\');
+                result.push(ch);
+            }
+            '\n' => result.push_str("\\newline{}"),
+            ch => result.push(ch),
+        }
+    }
+    result
+}
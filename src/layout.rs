@@ -0,0 +1,180 @@
+use super::{CustomEntry, Resume};
+use core::{
+    convert::TryFrom,
+    fmt::{Display, Formatter},
+};
+use serde::{Deserialize, Serialize};
+
+/// A section of a resume that can be moved or hidden. In documents a
+/// section is written by name, such as `"experience"`, and a custom
+/// section by its title, such as `"custom:Volunteering"`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum SectionId {
+    Skills,
+    Education,
+    Experience,
+    Projects,
+    Certifications,
+    Awards,
+    Publications,
+    // A custom section, by its title
+    Custom(String),
+}
+
+impl SectionId {
+    /// The sections every resume has, in their default order
+    pub fn built_in() -> [Self; 7] {
+        [
+            Self::Skills,
+            Self::Education,
+            Self::Experience,
+            Self::Projects,
+            Self::Certifications,
+            Self::Awards,
+            Self::Publications,
+        ]
+    }
+
+    /// The heading the section is shown under
+    pub fn title(&self) -> &str {
+        match self {
+            Self::Skills => "Skills",
+            Self::Education => "Education",
+            Self::Experience => "Professional Experience",
+            Self::Projects => "Projects",
+            Self::Certifications => "Certifications",
+            Self::Awards => "Awards",
+            Self::Publications => "Publications",
+            Self::Custom(title) => title,
+        }
+    }
+}
+
+impl Display for SectionId {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            Self::Skills => write!(f, "skills"),
+            Self::Education => write!(f, "education"),
+            Self::Experience => write!(f, "experience"),
+            Self::Projects => write!(f, "projects"),
+            Self::Certifications => write!(f, "certifications"),
+            Self::Awards => write!(f, "awards"),
+            Self::Publications => write!(f, "publications"),
+            Self::Custom(title) => write!(f, "custom:{}", title),
+        }
+    }
+}
+
+impl From<SectionId> for String {
+    fn from(id: SectionId) -> Self {
+        id.to_string()
+    }
+}
+
+impl TryFrom<String> for SectionId {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        if let Some(title) = name.strip_prefix("custom:") {
+            return Ok(Self::Custom(title.to_string()));
+        }
+        Self::built_in()
+            .iter()
+            .find(|id| id.to_string() == name)
+            .cloned()
+            .ok_or_else(|| {
+                format!(
+                    "`{}` is not a section, expected one of skills, education, experience, projects, certifications, awards, publications, or custom:<title>",
+                    name
+                )
+            })
+    }
+}
+
+/// Where a section goes in the layout, and whether it is shown
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LayoutEntry {
+    pub section: SectionId,
+    #[serde(default = "shown")]
+    pub visible: bool,
+}
+
+fn shown() -> bool {
+    true
+}
+
+/// The order sections of a resume are shown in, after the name and
+/// description. Sections left out of the list are shown at the end.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Layout {
+    #[serde(default)]
+    pub sections: Vec<LayoutEntry>,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            sections: SectionId::built_in()
+                .iter()
+                .map(|id| LayoutEntry {
+                    section: id.clone(),
+                    visible: true,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Resume {
+    /// Every section of this resume in the order of its layout, including
+    /// hidden ones. Custom sections missing from the layout come last, and
+    /// layout entries for custom sections that no longer exist are dropped.
+    pub fn arrangement(&self) -> Vec<LayoutEntry> {
+        let mut all: Vec<SectionId> = SectionId::built_in().to_vec();
+        for section in &self.custom_sections {
+            let id = SectionId::Custom(section.title.clone());
+            if !all.contains(&id) {
+                all.push(id);
+            }
+        }
+
+        let mut arrangement: Vec<LayoutEntry> = Vec::new();
+        for entry in &self.layout.sections {
+            if all.contains(&entry.section)
+                && !arrangement.iter().any(|e| e.section == entry.section)
+            {
+                arrangement.push(entry.clone());
+            }
+        }
+        for id in all {
+            if !arrangement.iter().any(|e| e.section == id) {
+                arrangement.push(LayoutEntry {
+                    section: id,
+                    visible: true,
+                });
+            }
+        }
+        arrangement
+    }
+
+    // The entries of the custom sections titled like `id`
+    pub(crate) fn custom_entries(&self, id: &SectionId) -> impl Iterator<Item = &CustomEntry> {
+        let title = id.title().to_string();
+        self.custom_sections
+            .iter()
+            .filter(move |section| section.title == title)
+            .flat_map(|section| &section.entries)
+    }
+
+    /// The sections that are shown, in order
+    pub fn visible_sections(&self) -> Vec<SectionId> {
+        self.arrangement()
+            .into_iter()
+            .filter(|entry| entry.visible)
+            .map(|entry| entry.section)
+            .collect()
+    }
+}
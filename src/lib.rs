@@ -1,12 +1,41 @@
+mod contrast;
+pub use contrast::{adjust_lightness, contrast_ratio, AccessibleTheme, ContrastIssue, AA_CONTRAST_RATIO};
+mod custom_theme;
+pub use custom_theme::{CustomTheme, SectionStyle};
+mod date;
+pub use date::Date;
+mod docx;
+mod document;
+pub use document::{Conversion, DocumentError, Format, DOCUMENT_VERSION};
 mod html;
 pub use html::HTML;
+mod europass;
+mod json_ld;
+mod json_resume;
+mod latex;
+mod layout;
+pub use layout::{Layout, LayoutEntry, SectionId};
+mod odt;
+mod pdf;
+pub use pdf::PdfError;
 mod resume;
 pub use resume::*;
-mod theme;
+mod stylesheet;
+mod template;
+pub use template::{ClassicTemplate, CompactTemplate, SidebarTemplate, Template, TimelineTemplate};
+mod text;
+pub mod theme;
 pub use theme::Theme;
+pub mod themes;
+pub use themes::ThemeOption;
+mod zip;
 
-/// Use named colors instead of RGB so that theme can control actual color values
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+use serde::Deserialize;
+
+/// Use named colors instead of RGB so that theme can control actual color values.
+/// Theme files name them in snake case, such as `default_title`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Color {
     Red,
     Pink,
@@ -26,6 +55,44 @@ pub enum Color {
     DefaultBackground,
 }
 
+impl Color {
+    pub fn all() -> [Self; 16] {
+        [
+            Self::Red,
+            Self::Pink,
+            Self::Orange,
+            Self::Yellow,
+            Self::Green,
+            Self::Blue,
+            Self::Violet,
+            Self::Brown,
+            Self::Black,
+            Self::White,
+            Self::Grey,
+            Self::DefaultTitle,
+            Self::DefaultSectionTitle,
+            Self::DefaultSubtitle,
+            Self::DefaultForeground,
+            Self::DefaultBackground,
+        ]
+    }
+
+    /// The colors resumes are written in, which must stand out against
+    /// `DefaultBackground` to be readable. Skill bars are filled with
+    /// `DefaultSectionTitle`, so they are held to the same contrast. `Grey`
+    /// is left out, since it only draws borders, shading and the empty part
+    /// of skill bars, never text.
+    pub fn text() -> [Self; 5] {
+        [
+            Self::DefaultTitle,
+            Self::DefaultSectionTitle,
+            Self::DefaultSubtitle,
+            Self::DefaultForeground,
+            Self::Red,
+        ]
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum HorizontalAlignment {
     Center,
@@ -1,103 +1,141 @@
 extern crate resume;
+mod cli;
+mod preview;
 use iced::{
-    button, scrollable, slider, text_input, Button, Checkbox, Column, Container, Element,
-    HorizontalAlignment, Image, Length, Radio, Row, Sandbox, Scrollable, Settings, Slider, Space,
-    Text, TextInput,
+    button, scrollable, text_input, Align, Button, Checkbox, Column, Container, Element,
+    HorizontalAlignment, Image, Length, Radio, Row, Sandbox, Scrollable, Settings, Space, Text,
+    TextInput,
+};
+use resume::{
+    non_empty, themes, AccessibleTheme, Award, Certification, ClassicTemplate, CompactTemplate,
+    ContactInfo, Conversion, CustomEntry, CustomSection, CustomTheme, Date, Degree, Education,
+    Layout, LayoutEntry, Proficiency, Project, Publication, Resume, SidebarTemplate, Template,
+    Theme, ThemeOption, TimelineTemplate, Work,
 };
-use resume::{Color, ContactInfo, Degree, Education, Proficiency, Resume, Theme, Work, HTML};
 use std::{
     cmp::{max, min},
-    fs::write,
+    fs::{read_to_string, write},
+    path::{Path, PathBuf},
 };
 
-pub struct DefaultTheme;
-impl Theme for DefaultTheme {
-    fn get_color_rgb(&self, color: Color) -> (u8, u8, u8) {
-        match color {
-            Color::DefaultForeground | Color::Black => (0, 0, 0),
-            Color::DefaultBackground | Color::White => (255, 255, 255),
-            Color::Red => (255, 0, 0),
-            Color::Green => (0, 255, 0),
-            Color::DefaultSectionTitle | Color::Blue => (0, 0, 255),
-            Color::Yellow => (255, 255, 0),
-            Color::Orange => (255, 165, 0),
-            Color::Pink => (255, 192, 203),
-            Color::DefaultSubtitle => (255, 0, 255),
-            Color::Grey => (128, 128, 128),
-            Color::DefaultTitle | Color::Violet => (127, 0, 255),
-            Color::Brown => (165, 42, 42),
-        }
-    }
-}
+// Themes are loaded from files in the directory named by this variable, or
+// else from the `themes` directory beside the executable
+const THEMES_DIR_VAR: &str = "RESUME_GEN_THEMES";
 
-pub struct DraculaTheme;
-impl Theme for DraculaTheme {
-    fn get_color_rgb(&self, color: Color) -> (u8, u8, u8) {
-        match color {
-            Color::Black | Color::DefaultBackground => (40, 42, 54),
-            Color::White | Color::DefaultForeground => (248, 248, 242),
-            Color::Red => (255, 85, 85),
-            Color::Orange => (255, 184, 108),
-            Color::Yellow => (241, 250, 140),
-            Color::Green => (80, 250, 123),
-            Color::DefaultSectionTitle | Color::Blue => (139, 233, 253),
-            Color::DefaultTitle | Color::Violet => (189, 147, 249),
-
-            Color::Grey => (68, 71, 90),
-            Color::DefaultSubtitle | Color::Pink => (255, 121, 198),
-            Color::Brown => (165, 42, 42),
-        }
+fn themes_dir() -> Option<PathBuf> {
+    match std::env::var_os(THEMES_DIR_VAR) {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => Some(std::env::current_exe().ok()?.parent()?.join("themes")),
     }
 }
 
-pub struct ForestTheme;
-impl Theme for ForestTheme {
-    fn get_color_rgb(&self, color: Color) -> (u8, u8, u8) {
-        match color {
-            Color::Black => (40, 42, 54),
-            Color::White | Color::DefaultForeground => (237, 245, 225), // #edf5e1
-            Color::Red => (255, 85, 85),
-            Color::Orange => (255, 184, 108),
-            Color::Yellow => (241, 250, 140),
-            Color::Green => (80, 250, 123),
-            Color::DefaultSectionTitle => (55, 150, 131), // #379683
-
-            Color::Blue | Color::DefaultBackground => (5, 56, 107), // #05386b
-            Color::DefaultTitle => (142, 228, 175),                 // #8ee4af
-            Color::Violet => (189, 147, 249),
-            Color::Grey => (68, 71, 90),
-            Color::DefaultSubtitle => (219, 92, 162), // #5cdb95
-            Color::Pink => (255, 121, 198),
-            Color::Brown => (165, 42, 42),
+// Add every theme in the themes directory to the registry after the built-in
+// ones. A file can't take the name of a theme that is already registered.
+fn register_custom_themes() {
+    let dir = match themes_dir() {
+        Some(dir) => dir,
+        None => return,
+    };
+    for (path, theme) in CustomTheme::load_dir(dir) {
+        match theme {
+            Ok(theme) => {
+                let name = theme.name().to_string();
+                if !themes::register(name.clone(), move || Box::new(theme.clone())) {
+                    eprintln!(
+                        "warning: skipping theme {}: a theme named {} already exists",
+                        path.display(),
+                        name
+                    );
+                }
+            }
+            Err(e) => eprintln!("warning: skipping theme {}: {}", path.display(), e),
         }
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub enum ThemeOption {
-    Default,
-    Dracula,
-    Forest,
+pub enum TemplateOption {
+    Classic,
+    Sidebar,
+    Compact,
+    Timeline,
 }
 
-impl From<ThemeOption> for String {
-    fn from(t: ThemeOption) -> Self {
-        format!("{:?}", t).replace("ThemeOption::", "")
+impl From<TemplateOption> for String {
+    fn from(t: TemplateOption) -> Self {
+        format!("{:?}", t).replace("TemplateOption::", "")
     }
 }
 
-impl ThemeOption {
-    fn all() -> [Self; 3] {
-        [Self::Default, Self::Dracula, Self::Forest]
+impl TemplateOption {
+    fn all() -> [Self; 4] {
+        [Self::Classic, Self::Sidebar, Self::Compact, Self::Timeline]
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::all()
+            .iter()
+            .cloned()
+            .find(|option| String::from(*option).eq_ignore_ascii_case(name))
     }
 
-    fn get_theme(&self) -> &dyn Theme {
+    fn get_template(&self) -> &dyn Template {
         match self {
-            Self::Default => &DefaultTheme,
-            Self::Dracula => &DraculaTheme,
-            Self::Forest => &ForestTheme,
+            Self::Classic => &ClassicTemplate,
+            Self::Sidebar => &SidebarTemplate,
+            Self::Compact => &CompactTemplate,
+            Self::Timeline => &TimelineTemplate,
+        }
+    }
+}
+
+// The column at which plain text exports are wrapped
+const TEXT_WIDTH: usize = 80;
+
+/// Compile a resume to the format named by the extension of `path`:
+/// PDF for `.pdf` files, Word for `.docx` files, OpenDocument for `.odt`
+/// files, LaTeX for `.tex` files, Markdown for `.md` files, plain text for
+/// `.txt` files, and HTML otherwise. Webpages can describe the person
+/// behind the resume in JSON-LD for search engines with `structured_data`.
+fn export(
+    resume: &Resume,
+    template: &dyn Template,
+    theme: &dyn Theme,
+    offline: bool,
+    structured_data: bool,
+    path: impl AsRef<Path>,
+) -> Result<Vec<u8>, String> {
+    let mut html = resume.generate_with(template);
+    if structured_data {
+        html = html.with_json_ld(resume.to_json_ld());
+    }
+    match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("pdf") => {
+            html.compile_pdf(theme).map_err(|e| e.to_string())
         }
+        Some(ext) if ext.eq_ignore_ascii_case("docx") => Ok(html.compile_docx(theme)),
+        Some(ext) if ext.eq_ignore_ascii_case("odt") => Ok(html.compile_odt(theme)),
+        Some(ext) if ext.eq_ignore_ascii_case("tex") => Ok(resume.to_latex(theme).into_bytes()),
+        Some(ext) if ext.eq_ignore_ascii_case("md") => Ok(resume.to_markdown().into_bytes()),
+        Some(ext) if ext.eq_ignore_ascii_case("txt") => {
+            Ok(resume.to_plain_text(TEXT_WIDTH).into_bytes())
+        }
+        _ if offline => Ok(html.compile_offline(theme).into_bytes()),
+        _ => Ok(html.compile(theme).into_bytes()),
+    }
+}
+
+/// Read a resume kept in another schema: a Europass CV for `.xml` files,
+/// and a JSON Resume otherwise
+fn import(path: impl AsRef<Path>) -> Result<Conversion<Resume>, String> {
+    let path = path.as_ref();
+    let text =
+        read_to_string(path).map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("xml") => Resume::from_europass(&text),
+        _ => Resume::from_json_resume(&text),
     }
+    .map_err(|e| e.to_string())
 }
 
 mod style {
@@ -140,10 +178,40 @@ fn button<'a, Message: Clone>(state: &'a mut button::State, label: &str) -> Butt
     .min_width(100)
 }
 
+// A compact button for controls repeated on every entry of a list
+fn small_button<'a, Message: Clone>(
+    state: &'a mut button::State,
+    label: &str,
+) -> Button<'a, Message> {
+    Button::new(state, Text::new(label).size(16))
+        .padding(6)
+        .style(style::Button::Secondary)
+}
+
+/// The buttons shown next to each entry of a list in the wizard
+#[derive(Debug, Clone, Default)]
+struct EntryControls {
+    edit: button::State,
+    up: button::State,
+    down: button::State,
+    delete: button::State,
+}
+
+/// A text input of a form in the wizard, with what has been typed into it
+#[derive(Debug, Clone, Default)]
+struct Field {
+    text: String,
+    state: text_input::State,
+}
+
+// The width of the wizard, next to which the preview fills the window
+const WIZARD_WIDTH: u16 = 580;
+
 #[derive(Clone, Debug)]
 pub struct App {
     steps: Steps,
     scroll: scrollable::State,
+    preview_scroll: scrollable::State,
     back_button: button::State,
     next_button: button::State,
 }
@@ -155,6 +223,7 @@ impl Sandbox for App {
         Self {
             steps: Steps::new(),
             scroll: scrollable::State::new(),
+            preview_scroll: scrollable::State::new(),
             back_button: button::State::new(),
             next_button: button::State::new(),
         }
@@ -168,15 +237,30 @@ impl Sandbox for App {
         match event {
             Message::NextPressed => self.steps.advance(),
             Message::BackPressed => self.steps.go_back(),
+            Message::ImportFile => self.steps.import(),
             other => self.steps.update(other),
         }
     }
 
-    fn view(&mut self) -> Element<Message> {
+    fn view(&mut self) -> Element<'_, Message> {
         let copy = self.clone();
+        let theme = copy.steps.theme().get_theme();
+        let accessible = AccessibleTheme::new(&*theme);
+        let preview = preview::preview(
+            &copy
+                .steps
+                .to_resume()
+                .generate_with(copy.steps.template().get_template()),
+            if copy.steps.fix_contrast() {
+                &accessible
+            } else {
+                &*theme
+            },
+        );
         let Self {
             steps,
             scroll,
+            preview_scroll,
             next_button,
             back_button,
             ..
@@ -210,12 +294,19 @@ impl Sandbox for App {
             .push(controls)
             .into();
 
-        let scrollable =
-            Scrollable::new(scroll).push(Container::new(content).width(Length::Fill).center_x());
+        let scrollable = Scrollable::new(scroll)
+            .width(Length::Units(WIZARD_WIDTH))
+            .push(Container::new(content).width(Length::Fill).center_x());
+
+        // The resume as it would currently be saved, updated on every change
+        let preview = Scrollable::new(preview_scroll)
+            .width(Length::Fill)
+            .padding(20)
+            .push(preview);
 
-        Container::new(scrollable)
-            .height(Length::Fill)
-            .center_y()
+        Row::new()
+            .push(Container::new(scrollable).height(Length::Fill).center_y())
+            .push(preview)
             .into()
     }
 }
@@ -227,23 +318,57 @@ pub enum Message {
 
     FirstNameChanged(String),
     LastNameChanged(String),
-    StartYearChanged(String),
-    EndYearChanged(String),
+    StartDateChanged(String),
+    EndDateChanged(String),
     PositionChanged(String),
     CompanyChanged(String),
     DescriptionChanged(String),
     DegreeSelected(Degree),
     ContactsChanged(ContactInfo),
+    ProjectNameChanged(String),
+    UrlChanged(String),
+    RepositoryChanged(String),
+    RoleChanged(String),
+    TechnologiesChanged(String),
+    HighlightChanged(String),
+    // Text typed into one of the fields of the current step's form
+    FieldChanged(usize, String),
+    SectionTitleChanged(String),
     ProficiencySelected(Proficiency),
     SaveFileChanged(String),
+    ImportFileChanged(String),
 
+    TemplateSelected(TemplateOption),
     ThemeSelected(ThemeOption),
+    OfflineToggled(bool),
+    StructuredDataToggled(bool),
+    FixContrastToggled(bool),
+    // Show or hide, or move, one section of the layout
+    SectionToggled(usize, bool),
+    MoveSectionUp(usize),
+    MoveSectionDown(usize),
 
     AddEducation,
     AddWork,
     AddSkill,
+    AddProject,
+    AddHighlight,
+    RemoveHighlight(usize),
+    AddCertification,
+    AddAward,
+    AddPublication,
+    AddCustomEntry,
+    RemoveCustomEntry(usize),
+    AddCustomSection,
     SaveFile,
+    ImportFile,
     Clear,
+
+    // Act on one entry of the list on the current step
+    EditEntry(usize),
+    DeleteEntry(usize),
+    MoveEntryUp(usize),
+    MoveEntryDown(usize),
 }
 
 #[derive(Debug, Clone)]
@@ -256,7 +381,12 @@ impl Steps {
     fn new() -> Self {
         Self {
             steps: vec![
-                Step::Welcome,
+                Step::Welcome {
+                    import_file: String::new(),
+                    import_file_state: text_input::State::new(),
+                    import_button_state: button::State::new(),
+                    status: None,
+                },
                 Step::Name {
                     first_name: String::new(),
                     last_name: String::new(),
@@ -268,6 +398,15 @@ impl Steps {
                     text: String::new(),
                     state: text_input::State::new(),
                 },
+                Step::ContactInfo {
+                    info: ContactInfo::default(),
+
+                    email_state: text_input::State::new(),
+                    phone_state: text_input::State::new(),
+                    website_state: text_input::State::new(),
+                    github_state: text_input::State::new(),
+                    linkedin_state: text_input::State::new(),
+                },
                 Step::Description {
                     text: String::new(),
                     state: text_input::State::new(),
@@ -279,6 +418,8 @@ impl Steps {
                     text_state: text_input::State::new(),
                     button_state: button::State::new(),
                     clear_state: button::State::new(),
+                    editing: None,
+                    controls: Vec::new(),
                 },
                 Step::Education {
                     education_history: Vec::new(),
@@ -287,15 +428,18 @@ impl Steps {
 
                     field: String::new(),
                     field_state: text_input::State::new(),
-                    start_year: String::new(),
-                    start_year_state: text_input::State::new(),
-                    end_year: String::new(),
-                    end_year_state: text_input::State::new(),
+                    start_date: String::new(),
+                    start_date_state: text_input::State::new(),
+                    end_date: String::new(),
+                    end_date_state: text_input::State::new(),
                     school: String::new(),
                     school_state: text_input::State::new(),
 
                     button_state: button::State::new(),
                     clear_state: button::State::new(),
+                    editing: None,
+                    controls: Vec::new(),
+                    status: None,
                 },
                 Step::Work {
                     work_history: Vec::new(),
@@ -306,41 +450,130 @@ impl Steps {
                     position_state: text_input::State::new(),
                     description: String::new(),
                     description_state: text_input::State::new(),
-                    start_year: String::new(),
-                    start_year_state: text_input::State::new(),
-                    end_year: String::new(),
-                    end_year_state: text_input::State::new(),
+                    start_date: String::new(),
+                    start_date_state: text_input::State::new(),
+                    end_date: String::new(),
+                    end_date_state: text_input::State::new(),
+                    highlights: Vec::new(),
+                    highlight: String::new(),
+                    highlight_state: text_input::State::new(),
+                    highlight_button_state: button::State::new(),
+                    highlight_controls: Vec::new(),
+
+                    button_state: button::State::new(),
+                    clear_state: button::State::new(),
+                    editing: None,
+                    controls: Vec::new(),
+                    status: None,
+                },
+                Step::Projects {
+                    projects: Vec::new(),
+
+                    name: String::new(),
+                    name_state: text_input::State::new(),
+                    url: String::new(),
+                    url_state: text_input::State::new(),
+                    repository: String::new(),
+                    repository_state: text_input::State::new(),
+                    role: String::new(),
+                    role_state: text_input::State::new(),
+                    start_date: String::new(),
+                    start_date_state: text_input::State::new(),
+                    end_date: String::new(),
+                    end_date_state: text_input::State::new(),
+                    technologies: String::new(),
+                    technologies_state: text_input::State::new(),
+                    highlights: Vec::new(),
+                    highlight: String::new(),
+                    highlight_state: text_input::State::new(),
+                    highlight_button_state: button::State::new(),
+                    highlight_controls: Vec::new(),
+
+                    button_state: button::State::new(),
+                    clear_state: button::State::new(),
+                    editing: None,
+                    controls: Vec::new(),
+                    status: None,
+                },
+                Step::Certifications {
+                    certifications: Vec::new(),
+                    fields: Default::default(),
+                    button_state: button::State::new(),
+                    clear_state: button::State::new(),
+                    editing: None,
+                    controls: Vec::new(),
+                    status: None,
+                },
+                Step::Awards {
+                    awards: Vec::new(),
+                    fields: Default::default(),
+                    button_state: button::State::new(),
+                    clear_state: button::State::new(),
+                    editing: None,
+                    controls: Vec::new(),
+                    status: None,
+                },
+                Step::Publications {
+                    publications: Vec::new(),
+                    fields: Default::default(),
+                    button_state: button::State::new(),
+                    clear_state: button::State::new(),
+                    editing: None,
+                    controls: Vec::new(),
+                    status: None,
+                },
+                Step::CustomSections {
+                    sections: Vec::new(),
+                    title: Field::default(),
+                    entries: Vec::new(),
+                    entry_controls: Vec::new(),
+                    fields: Default::default(),
+                    highlights: Vec::new(),
+                    highlight: String::new(),
+                    highlight_state: text_input::State::new(),
+                    highlight_button_state: button::State::new(),
+                    highlight_controls: Vec::new(),
+                    entry_button_state: button::State::new(),
 
                     button_state: button::State::new(),
                     clear_state: button::State::new(),
+                    editing: None,
+                    controls: Vec::new(),
+                    status: None,
                 },
                 Step::End {
-                    theme: ThemeOption::Default,
+                    template: TemplateOption::Classic,
+                    theme: ThemeOption::default(),
                     save_file: String::from("resume.html"),
                     save_file_state: text_input::State::new(),
                     button_state: button::State::new(),
+                    offline: false,
+                    structured_data: false,
+                    fix_contrast: false,
+                    layout: Layout::default(),
+                    layout_controls: Vec::new(),
+                    status: None,
                 },
             ],
             current: 0,
         }
     }
 
-    fn to_resume(&self) -> String {
+    fn to_resume(&self) -> Resume {
         let mut first_name = String::new();
         let mut last_name = String::new();
         let mut profession = String::new();
         let mut description = String::new();
-        let mut contact_info = ContactInfo {
-            email: None,
-            phone: None,
-            github: None,
-            website: None,
-            linkedin: None,
-        };
+        let mut contact_info = ContactInfo::default();
         let mut skills = Vec::new();
         let mut work_experience = Vec::new();
         let mut education = Vec::new();
-        let mut theme = ThemeOption::Default;
+        let mut projects = Vec::new();
+        let mut certifications = Vec::new();
+        let mut awards = Vec::new();
+        let mut publications = Vec::new();
+        let mut custom_sections = Vec::new();
+        let mut layout = Layout::default();
 
         for step in &self.steps {
             match step {
@@ -355,6 +588,9 @@ impl Steps {
                 Step::Profession { text, .. } => {
                     profession = text.clone();
                 }
+                Step::ContactInfo { info, .. } => {
+                    contact_info = info.clone();
+                }
                 Step::Description { text, .. } => {
                     description = text.clone();
                 }
@@ -369,7 +605,28 @@ impl Steps {
                 } => {
                     education = education_history.clone();
                 }
-                Step::End { theme: t, .. } => theme = *t,
+                Step::Projects { projects: p, .. } => {
+                    projects = p.clone();
+                }
+                Step::Certifications {
+                    certifications: c, ..
+                } => {
+                    certifications = c.clone();
+                }
+                Step::Awards { awards: a, .. } => {
+                    awards = a.clone();
+                }
+                Step::Publications {
+                    publications: p, ..
+                } => {
+                    publications = p.clone();
+                }
+                Step::CustomSections { sections, .. } => {
+                    custom_sections = sections.clone();
+                }
+                Step::End { layout: l, .. } => {
+                    layout = l.clone();
+                }
                 _ => {}
             }
         }
@@ -383,15 +640,132 @@ impl Steps {
             skills,
             work_experience,
             education,
+            projects,
+            certifications,
+            awards,
+            publications,
+            custom_sections,
+            layout,
+        }
+    }
+
+    // The wizard with every step filled in from an existing resume
+    fn load(resume: Resume) -> Self {
+        let mut loaded = Self::new();
+        for step in &mut loaded.steps {
+            match step {
+                Step::Name {
+                    first_name,
+                    last_name,
+                    ..
+                } => {
+                    *first_name = resume.first_name.clone();
+                    *last_name = resume.last_name.clone();
+                }
+                Step::Profession { text, .. } => {
+                    *text = resume.profession.clone();
+                }
+                Step::ContactInfo { info, .. } => {
+                    *info = resume.contact_info.clone();
+                }
+                Step::Description { text, .. } => {
+                    *text = resume.description.clone();
+                }
+                Step::Skills { skills, .. } => {
+                    *skills = resume.skills.clone();
+                }
+                Step::Work { work_history, .. } => {
+                    *work_history = resume.work_experience.clone();
+                }
+                Step::Education {
+                    education_history, ..
+                } => {
+                    *education_history = resume.education.clone();
+                }
+                Step::Projects { projects, .. } => {
+                    *projects = resume.projects.clone();
+                }
+                Step::Certifications { certifications, .. } => {
+                    *certifications = resume.certifications.clone();
+                }
+                Step::Awards { awards, .. } => {
+                    *awards = resume.awards.clone();
+                }
+                Step::Publications { publications, .. } => {
+                    *publications = resume.publications.clone();
+                }
+                Step::CustomSections { sections, .. } => {
+                    *sections = resume.custom_sections.clone();
+                }
+                Step::End { layout, .. } => {
+                    *layout = resume.layout.clone();
+                }
+                Step::Welcome { .. } => {}
+            }
+        }
+        loaded
+    }
+
+    // Replace everything entered so far with the JSON Resume or Europass CV
+    // named on the Welcome step, reporting what could not be brought over
+    fn import(&mut self) {
+        let path = match &self.steps[0] {
+            Step::Welcome { import_file, .. } => import_file.clone(),
+            _ => return,
+        };
+        let outcome = match import(&path) {
+            Ok(conversion) => {
+                let welcome = self.steps[0].clone();
+                *self = Self::load(conversion.value);
+                self.steps[0] = welcome;
+                if conversion.unmapped.is_empty() {
+                    format!("Imported {}", path)
+                } else {
+                    format!(
+                        "Imported {}, but left out {}",
+                        path,
+                        conversion.unmapped.join(", ")
+                    )
+                }
+            }
+            Err(e) => format!("Could not import {}: {}", path, e),
+        };
+        if let Step::Welcome { status, .. } = &mut self.steps[0] {
+            *status = Some(outcome);
         }
-        .generate()
-        .compile(theme.get_theme())
     }
 
     fn title(&self) -> String {
         self.steps[self.current].title().to_string()
     }
 
+    fn template(&self) -> TemplateOption {
+        self.steps
+            .iter()
+            .find_map(|step| match step {
+                Step::End { template, .. } => Some(*template),
+                _ => None,
+            })
+            .unwrap_or(TemplateOption::Classic)
+    }
+
+    fn theme(&self) -> ThemeOption {
+        self.steps
+            .iter()
+            .find_map(|step| match step {
+                Step::End { theme, .. } => Some(*theme),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    fn fix_contrast(&self) -> bool {
+        self.steps.iter().any(|step| match step {
+            Step::End { fix_contrast, .. } => *fix_contrast,
+            _ => false,
+        })
+    }
+
     fn advance(&mut self) {
         self.current += 1;
         self.current = min(self.current, self.steps.len() - 1);
@@ -413,7 +787,7 @@ impl Steps {
         self.has_next() && self.steps[self.current].can_continue()
     }
 
-    fn view(&mut self, app: &App) -> Element<Message> {
+    fn view(&mut self, app: &App) -> Element<'_, Message> {
         self.steps[self.current].view(app)
     }
 
@@ -425,7 +799,14 @@ impl Steps {
 
 #[derive(Debug, Clone)]
 enum Step {
-    Welcome,
+    Welcome {
+        // A JSON Resume or Europass XML CV to start from
+        import_file: String,
+        import_file_state: text_input::State,
+        import_button_state: button::State,
+        // The outcome of the last attempt to import a resume
+        status: Option<String>,
+    },
 
     Name {
         first_name: String,
@@ -462,6 +843,9 @@ enum Step {
         text_state: text_input::State,
         button_state: button::State,
         clear_state: button::State,
+        // The index of the entry loaded into the inputs, if any
+        editing: Option<usize>,
+        controls: Vec<EntryControls>,
     },
 
     Education {
@@ -471,24 +855,28 @@ enum Step {
 
         field: String,
         field_state: text_input::State,
-        start_year: String,
-        start_year_state: text_input::State,
-        end_year: String,
-        end_year_state: text_input::State,
+        start_date: String,
+        start_date_state: text_input::State,
+        end_date: String,
+        end_date_state: text_input::State,
         school: String,
         school_state: text_input::State,
 
         button_state: button::State,
         clear_state: button::State,
+        editing: Option<usize>,
+        controls: Vec<EntryControls>,
+        // Why the last entry could not be added, if it could not
+        status: Option<String>,
     },
 
     Work {
         work_history: Vec<Work>,
 
-        start_year: String,
-        start_year_state: text_input::State,
-        end_year: String,
-        end_year_state: text_input::State,
+        start_date: String,
+        start_date_state: text_input::State,
+        end_date: String,
+        end_date_state: text_input::State,
 
         position: String,
         position_state: text_input::State,
@@ -496,16 +884,134 @@ enum Step {
         company_state: text_input::State,
         description: String,
         description_state: text_input::State,
+        // The highlights of the job being entered, added one at a time
+        highlights: Vec<String>,
+        highlight: String,
+        highlight_state: text_input::State,
+        highlight_button_state: button::State,
+        // The button to remove each highlight
+        highlight_controls: Vec<button::State>,
+
+        button_state: button::State,
+        clear_state: button::State,
+        editing: Option<usize>,
+        controls: Vec<EntryControls>,
+        // Why the last entry could not be added, if it could not
+        status: Option<String>,
+    },
+
+    Projects {
+        projects: Vec<Project>,
+
+        name: String,
+        name_state: text_input::State,
+        url: String,
+        url_state: text_input::State,
+        repository: String,
+        repository_state: text_input::State,
+        role: String,
+        role_state: text_input::State,
+        start_date: String,
+        start_date_state: text_input::State,
+        end_date: String,
+        end_date_state: text_input::State,
+        // Separated by commas
+        technologies: String,
+        technologies_state: text_input::State,
+        // The highlights of the project being entered, added one at a time
+        highlights: Vec<String>,
+        highlight: String,
+        highlight_state: text_input::State,
+        highlight_button_state: button::State,
+        highlight_controls: Vec<button::State>,
+
+        button_state: button::State,
+        clear_state: button::State,
+        editing: Option<usize>,
+        controls: Vec<EntryControls>,
+        // Why the last entry could not be added, if it could not
+        status: Option<String>,
+    },
+
+    Certifications {
+        certifications: Vec<Certification>,
+        // Name, issuer, credential ID, issue date, expiry date and URL
+        fields: [Field; 6],
+        button_state: button::State,
+        clear_state: button::State,
+        editing: Option<usize>,
+        controls: Vec<EntryControls>,
+        // Why the last entry could not be added, if it could not
+        status: Option<String>,
+    },
+
+    Awards {
+        awards: Vec<Award>,
+        // Title, issuer, date and summary
+        fields: [Field; 4],
+        button_state: button::State,
+        clear_state: button::State,
+        editing: Option<usize>,
+        controls: Vec<EntryControls>,
+        // Why the last entry could not be added, if it could not
+        status: Option<String>,
+    },
+
+    Publications {
+        publications: Vec<Publication>,
+        // Title, authors, venue, year, DOI and URL
+        fields: [Field; 6],
+        button_state: button::State,
+        clear_state: button::State,
+        editing: Option<usize>,
+        controls: Vec<EntryControls>,
+        // Why the last entry could not be added, if it could not
+        status: Option<String>,
+    },
+
+    CustomSections {
+        sections: Vec<CustomSection>,
+
+        // The section being put together
+        title: Field,
+        entries: Vec<CustomEntry>,
+        // The button to remove each entry of the section
+        entry_controls: Vec<button::State>,
+        // Heading, subheading, start date, end date and location of the entry being entered
+        fields: [Field; 5],
+        // The bullets of the entry being entered
+        highlights: Vec<String>,
+        highlight: String,
+        highlight_state: text_input::State,
+        highlight_button_state: button::State,
+        highlight_controls: Vec<button::State>,
+        entry_button_state: button::State,
 
         button_state: button::State,
         clear_state: button::State,
+        editing: Option<usize>,
+        controls: Vec<EntryControls>,
+        // Why the last entry could not be added, if it could not
+        status: Option<String>,
     },
 
     End {
+        template: TemplateOption,
         theme: ThemeOption,
         save_file: String,
         save_file_state: text_input::State,
         button_state: button::State,
+        // Inline all styles instead of linking them from CDNs
+        offline: bool,
+        // Describe the person in JSON-LD for search engines
+        structured_data: bool,
+        // Adjust text colors that are too faint against the background
+        fix_contrast: bool,
+        layout: Layout,
+        // The buttons to move each section up and down
+        layout_controls: Vec<(button::State, button::State)>,
+        // The outcome of the last attempt to save the resume
+        status: Option<String>,
     },
 }
 
@@ -522,15 +1028,19 @@ impl<'a> Step {
                     *last_name = name;
                 }
             }
-            Message::StartYearChanged(year) => match self {
-                Self::Work { start_year, .. } | Self::Education { start_year, .. } => {
-                    *start_year = year;
+            Message::StartDateChanged(date) => match self {
+                Self::Work { start_date, .. }
+                | Self::Education { start_date, .. }
+                | Self::Projects { start_date, .. } => {
+                    *start_date = date;
                 }
                 _ => unreachable!(),
             },
-            Message::EndYearChanged(year) => match self {
-                Self::Work { end_year, .. } | Self::Education { end_year, .. } => {
-                    *end_year = year;
+            Message::EndDateChanged(date) => match self {
+                Self::Work { end_date, .. }
+                | Self::Education { end_date, .. }
+                | Self::Projects { end_date, .. } => {
+                    *end_date = date;
                 }
                 _ => unreachable!(),
             },
@@ -562,6 +1072,236 @@ impl<'a> Step {
                     *info = contacts;
                 }
             }
+            Message::ProjectNameChanged(text) => {
+                if let Self::Projects { name, .. } = self {
+                    *name = text;
+                }
+            }
+            Message::UrlChanged(text) => {
+                if let Self::Projects { url, .. } = self {
+                    *url = text;
+                }
+            }
+            Message::RepositoryChanged(text) => {
+                if let Self::Projects { repository, .. } = self {
+                    *repository = text;
+                }
+            }
+            Message::RoleChanged(text) => {
+                if let Self::Projects { role, .. } = self {
+                    *role = text;
+                }
+            }
+            Message::TechnologiesChanged(text) => {
+                if let Self::Projects { technologies, .. } = self {
+                    *technologies = text;
+                }
+            }
+            Message::HighlightChanged(text) => match self {
+                Self::Work { highlight, .. }
+                | Self::Projects { highlight, .. }
+                | Self::CustomSections { highlight, .. } => {
+                    *highlight = text;
+                }
+                _ => unreachable!(),
+            },
+            Message::AddHighlight => match self {
+                Self::Work {
+                    highlights,
+                    highlight,
+                    highlight_state,
+                    ..
+                }
+                | Self::Projects {
+                    highlights,
+                    highlight,
+                    highlight_state,
+                    ..
+                }
+                | Self::CustomSections {
+                    highlights,
+                    highlight,
+                    highlight_state,
+                    ..
+                } => {
+                    // Enter submits the input even when nothing was typed
+                    if !highlight.trim().is_empty() {
+                        highlights.push(highlight.trim().to_string());
+                    }
+                    *highlight = String::new();
+                    *highlight_state = text_input::State::new();
+                }
+                _ => unreachable!(),
+            },
+            Message::RemoveHighlight(index) => match self {
+                Self::Work { highlights, .. }
+                | Self::Projects { highlights, .. }
+                | Self::CustomSections { highlights, .. } => {
+                    highlights.remove(index);
+                }
+                _ => unreachable!(),
+            },
+            Message::AddProject => {
+                if let Self::Projects {
+                    projects,
+                    name,
+                    url,
+                    repository,
+                    role,
+                    start_date,
+                    end_date,
+                    technologies,
+                    highlights,
+                    highlight,
+                    editing,
+                    status,
+                    ..
+                } = self
+                {
+                    let (start, end) = match parse_optional_range(start_date, end_date) {
+                        Ok(range) => range,
+                        Err(e) => {
+                            *status = Some(e);
+                            return;
+                        }
+                    };
+                    place_entry(
+                        projects,
+                        editing,
+                        Project {
+                            name: name.clone(),
+                            url: non_empty(url),
+                            repository: non_empty(repository),
+                            role: non_empty(role),
+                            start,
+                            end,
+                            technologies: technologies
+                                .split(',')
+                                .map(str::trim)
+                                .filter(|t| !t.is_empty())
+                                .map(String::from)
+                                .collect(),
+                            highlights: with_pending(highlights, highlight),
+                        },
+                    );
+                    *status = None;
+                    self.clear_project_inputs();
+                }
+            }
+            Message::FieldChanged(index, text) => {
+                if let Some(fields) = self.fields() {
+                    fields[index].text = text;
+                }
+            }
+            Message::AddCertification => {
+                if let Self::Certifications {
+                    certifications,
+                    fields,
+                    editing,
+                    status,
+                    ..
+                } = self
+                {
+                    match read_certification(fields) {
+                        Ok(entry) => {
+                            place_entry(certifications, editing, entry);
+                            *fields = Default::default();
+                            *status = None;
+                        }
+                        Err(e) => *status = Some(e),
+                    }
+                }
+            }
+            Message::AddAward => {
+                if let Self::Awards {
+                    awards,
+                    fields,
+                    editing,
+                    status,
+                    ..
+                } = self
+                {
+                    match read_award(fields) {
+                        Ok(entry) => {
+                            place_entry(awards, editing, entry);
+                            *fields = Default::default();
+                            *status = None;
+                        }
+                        Err(e) => *status = Some(e),
+                    }
+                }
+            }
+            Message::AddPublication => {
+                if let Self::Publications {
+                    publications,
+                    fields,
+                    editing,
+                    status,
+                    ..
+                } = self
+                {
+                    match read_publication(fields) {
+                        Ok(entry) => {
+                            place_entry(publications, editing, entry);
+                            *fields = Default::default();
+                            *status = None;
+                        }
+                        Err(e) => *status = Some(e),
+                    }
+                }
+            }
+            Message::SectionTitleChanged(text) => {
+                if let Self::CustomSections { title, .. } = self {
+                    title.text = text;
+                }
+            }
+            Message::AddCustomEntry => {
+                if let Self::CustomSections {
+                    entries,
+                    fields,
+                    highlights,
+                    highlight,
+                    status,
+                    ..
+                } = self
+                {
+                    let bullets = with_pending(highlights, highlight);
+                    match read_custom_entry(fields, &bullets) {
+                        Ok(entry) => {
+                            entries.push(entry);
+                            *status = None;
+                            self.clear_custom_entry_inputs();
+                        }
+                        Err(e) => *status = Some(e),
+                    }
+                }
+            }
+            Message::RemoveCustomEntry(index) => {
+                if let Self::CustomSections { entries, .. } = self {
+                    entries.remove(index);
+                }
+            }
+            Message::AddCustomSection => {
+                if let Self::CustomSections {
+                    sections,
+                    title,
+                    entries,
+                    editing,
+                    ..
+                } = self
+                {
+                    place_entry(
+                        sections,
+                        editing,
+                        CustomSection {
+                            title: title.text.trim().to_string(),
+                            entries: std::mem::take(entries),
+                        },
+                    );
+                    *title = Field::default();
+                    self.clear_custom_entry_inputs();
+                }
+            }
             Message::ProficiencySelected(prof) => {
                 if let Self::Skills { selection, .. } = self {
                     *selection = Some(prof);
@@ -573,28 +1313,105 @@ impl<'a> Step {
                     selection,
                     text,
                     text_state,
+                    editing,
                     ..
                 } = self
                 {
-                    skills.push((text.clone(), *selection));
+                    place_entry(skills, editing, (text.clone(), *selection));
                     *selection = None;
                     *text = String::new();
                     *text_state = text_input::State::new();
                 }
             }
+            Message::TemplateSelected(t) => {
+                if let Self::End { template, .. } = self {
+                    *template = t;
+                }
+            }
             Message::ThemeSelected(t) => {
                 if let Self::End { theme, .. } = self {
                     *theme = t;
                 }
             }
+            Message::OfflineToggled(value) => {
+                if let Self::End { offline, .. } = self {
+                    *offline = value;
+                }
+            }
+            Message::StructuredDataToggled(value) => {
+                if let Self::End {
+                    structured_data, ..
+                } = self
+                {
+                    *structured_data = value;
+                }
+            }
+            Message::FixContrastToggled(value) => {
+                if let Self::End { fix_contrast, .. } = self {
+                    *fix_contrast = value;
+                }
+            }
+            Message::SectionToggled(index, visible) => {
+                if let Self::End { layout, .. } = self {
+                    // Custom sections may have changed since the layout was last edited
+                    let mut sections = steps.to_resume().arrangement();
+                    sections[index].visible = visible;
+                    layout.sections = sections;
+                }
+            }
+            Message::MoveSectionUp(index) => {
+                if let Self::End { layout, .. } = self {
+                    let mut sections = steps.to_resume().arrangement();
+                    sections.swap(index - 1, index);
+                    layout.sections = sections;
+                }
+            }
+            Message::MoveSectionDown(index) => {
+                if let Self::End { layout, .. } = self {
+                    let mut sections = steps.to_resume().arrangement();
+                    sections.swap(index, index + 1);
+                    layout.sections = sections;
+                }
+            }
+            Message::ImportFileChanged(name) => {
+                if let Self::Welcome { import_file, .. } = self {
+                    *import_file = name;
+                }
+            }
             Message::SaveFileChanged(name) => {
                 if let Self::End { save_file, .. } = self {
                     *save_file = name;
                 }
             }
             Message::SaveFile => {
-                if let Self::End { save_file, .. } = self {
-                    write(&save_file, steps.to_resume()).unwrap();
+                if let Self::End {
+                    template,
+                    theme,
+                    offline,
+                    structured_data,
+                    fix_contrast,
+                    save_file,
+                    status,
+                    ..
+                } = self
+                {
+                    let theme = theme.get_theme();
+                    let accessible = AccessibleTheme::new(&*theme);
+                    *status = Some(
+                        match export(
+                            &steps.to_resume(),
+                            template.get_template(),
+                            if *fix_contrast { &accessible } else { &*theme },
+                            *offline,
+                            *structured_data,
+                            &*save_file,
+                        )
+                        .and_then(|bytes| write(&*save_file, bytes).map_err(|e| e.to_string()))
+                        {
+                            Ok(()) => format!("Saved your resume to {}", save_file),
+                            Err(e) => format!("Could not save to {}: {}", save_file, e),
+                        },
+                    );
                 }
             }
             Message::AddEducation => {
@@ -605,34 +1422,48 @@ impl<'a> Step {
                     field,
                     field_state,
                     degree,
-                    start_year,
-                    start_year_state,
-                    end_year,
-                    end_year_state,
+                    start_date,
+                    start_date_state,
+                    end_date,
+                    end_date_state,
+                    editing,
+                    status,
                     ..
                 } = self
                 {
-                    education_history.push(Education {
-                        school: school.clone(),
-                        field: if field.is_empty() {
-                            None
-                        } else {
-                            Some(field.clone())
+                    let (start, end) = match parse_range(start_date, end_date) {
+                        Ok(range) => range,
+                        Err(e) => {
+                            *status = Some(e);
+                            return;
+                        }
+                    };
+                    place_entry(
+                        education_history,
+                        editing,
+                        Education {
+                            school: school.clone(),
+                            field: if field.is_empty() {
+                                None
+                            } else {
+                                Some(field.clone())
+                            },
+                            degree: *degree,
+                            start,
+                            end,
                         },
-                        degree: degree.clone(),
-                        start_year: start_year.parse::<u32>().unwrap(),
-                        end_year: end_year.parse::<u32>().unwrap(),
-                    });
+                    );
+                    *status = None;
                     *school = String::new();
                     *field = String::new();
                     *degree = None;
-                    *start_year = String::new();
-                    *end_year = String::new();
+                    *start_date = String::new();
+                    *end_date = String::new();
 
                     *school_state = text_input::State::new();
                     *field_state = text_input::State::new();
-                    *start_year_state = text_input::State::new();
-                    *end_year_state = text_input::State::new();
+                    *start_date_state = text_input::State::new();
+                    *end_date_state = text_input::State::new();
                 }
             }
             Message::AddWork => {
@@ -644,31 +1475,52 @@ impl<'a> Step {
                     position_state,
                     description,
                     description_state,
-                    start_year,
-                    start_year_state,
-                    end_year,
-                    end_year_state,
+                    start_date,
+                    start_date_state,
+                    end_date,
+                    end_date_state,
+                    highlights,
+                    highlight,
+                    highlight_state,
+                    editing,
+                    status,
                     ..
                 } = self
                 {
-                    work_history.push(Work {
-                        company: company.clone(),
-                        position: position.clone(),
-                        description: description.clone(),
-                        start_year: start_year.parse::<u32>().unwrap(),
-                        end_year: end_year.parse::<u32>().unwrap(),
-                    });
+                    let (start, end) = match parse_range(start_date, end_date) {
+                        Ok(range) => range,
+                        Err(e) => {
+                            *status = Some(e);
+                            return;
+                        }
+                    };
+                    place_entry(
+                        work_history,
+                        editing,
+                        Work {
+                            company: company.clone(),
+                            position: position.clone(),
+                            description: description.trim().to_string(),
+                            highlights: with_pending(highlights, highlight),
+                            start,
+                            end,
+                        },
+                    );
+                    *status = None;
                     *company = String::new();
                     *position = String::new();
                     *description = String::new();
-                    *start_year = String::new();
-                    *end_year = String::new();
+                    *start_date = String::new();
+                    *end_date = String::new();
+                    *highlights = Vec::new();
+                    *highlight = String::new();
 
                     *company_state = text_input::State::new();
                     *position_state = text_input::State::new();
                     *description_state = text_input::State::new();
-                    *start_year_state = text_input::State::new();
-                    *end_year_state = text_input::State::new();
+                    *start_date_state = text_input::State::new();
+                    *end_date_state = text_input::State::new();
+                    *highlight_state = text_input::State::new();
                 }
             }
             Message::Clear => match self {
@@ -677,9 +1529,13 @@ impl<'a> Step {
                     selection,
                     text,
                     text_state,
+                    editing,
                     ..
                 } => {
-                    *skills = Vec::new();
+                    // While editing, only the entry being edited is abandoned
+                    if editing.take().is_none() {
+                        *skills = Vec::new();
+                    }
                     *selection = None;
                     *text = String::new();
                     *text_state = text_input::State::new();
@@ -691,23 +1547,26 @@ impl<'a> Step {
                     field,
                     field_state,
                     degree,
-                    start_year,
-                    start_year_state,
-                    end_year,
-                    end_year_state,
+                    start_date,
+                    start_date_state,
+                    end_date,
+                    end_date_state,
+                    editing,
                     ..
                 } => {
-                    *education_history = Vec::new();
+                    if editing.take().is_none() {
+                        *education_history = Vec::new();
+                    }
                     *school = String::new();
                     *field = String::new();
                     *degree = None;
-                    *start_year = String::new();
-                    *end_year = String::new();
+                    *start_date = String::new();
+                    *end_date = String::new();
 
                     *school_state = text_input::State::new();
                     *field_state = text_input::State::new();
-                    *start_year_state = text_input::State::new();
-                    *end_year_state = text_input::State::new();
+                    *start_date_state = text_input::State::new();
+                    *end_date_state = text_input::State::new();
                 }
                 Self::Work {
                     work_history,
@@ -717,69 +1576,506 @@ impl<'a> Step {
                     position_state,
                     description,
                     description_state,
-                    start_year,
-                    start_year_state,
-                    end_year,
-                    end_year_state,
+                    start_date,
+                    start_date_state,
+                    end_date,
+                    end_date_state,
+                    highlights,
+                    highlight,
+                    highlight_state,
+                    editing,
                     ..
                 } => {
-                    *work_history = Vec::new();
+                    if editing.take().is_none() {
+                        *work_history = Vec::new();
+                    }
                     *company = String::new();
                     *position = String::new();
                     *description = String::new();
-                    *start_year = String::new();
-                    *end_year = String::new();
+                    *start_date = String::new();
+                    *end_date = String::new();
+                    *highlights = Vec::new();
+                    *highlight = String::new();
 
                     *company_state = text_input::State::new();
                     *position_state = text_input::State::new();
                     *description_state = text_input::State::new();
-                    *start_year_state = text_input::State::new();
-                    *end_year_state = text_input::State::new();
+                    *start_date_state = text_input::State::new();
+                    *end_date_state = text_input::State::new();
+                    *highlight_state = text_input::State::new();
+                }
+                Self::Projects {
+                    projects, editing, ..
+                } => {
+                    if editing.take().is_none() {
+                        *projects = Vec::new();
+                    }
+                    self.clear_project_inputs();
+                }
+                Self::Certifications {
+                    certifications,
+                    editing,
+                    ..
+                } => {
+                    if editing.take().is_none() {
+                        *certifications = Vec::new();
+                    }
+                    self.clear_fields();
+                }
+                Self::Awards {
+                    awards, editing, ..
+                } => {
+                    if editing.take().is_none() {
+                        *awards = Vec::new();
+                    }
+                    self.clear_fields();
+                }
+                Self::Publications {
+                    publications,
+                    editing,
+                    ..
+                } => {
+                    if editing.take().is_none() {
+                        *publications = Vec::new();
+                    }
+                    self.clear_fields();
+                }
+                Self::CustomSections {
+                    sections,
+                    title,
+                    entries,
+                    editing,
+                    ..
+                } => {
+                    if editing.take().is_none() {
+                        *sections = Vec::new();
+                    }
+                    *title = Field::default();
+                    *entries = Vec::new();
+                    self.clear_custom_entry_inputs();
                 }
                 _ => unreachable!(),
             },
-            Message::NextPressed | Message::BackPressed => {}
-        }
-    }
-
-    fn title(&self) -> &str {
-        match self {
-            Self::Welcome => "Welcome",
-            Self::Name { .. } => "Name",
-            Self::Profession { .. } => "Profession",
-            Self::ContactInfo { .. } => "Contact Information",
-            Self::Description { .. } => "Description",
-            Self::Skills { .. } => "Skills",
-            Self::Education { .. } => "Education",
-            Self::Work { .. } => "Work",
-            Self::End { .. } => "End",
-        }
-    }
-
-    fn can_continue(&self) -> bool {
-        match self {
-            Self::Welcome | Self::ContactInfo { .. } | Self::Skills { .. } => true,
-            Self::Name {
-                first_name,
-                last_name,
-                ..
-            } => !first_name.is_empty() && !last_name.is_empty(),
-            Self::Profession { text, .. } | Self::Description { text, .. } => !text.is_empty(),
-            Self::Education { .. } => true,
-            Self::Work { .. } => true,
-            Self::End { .. } => false,
-        }
-    }
-
-    fn view(&mut self, app: &App) -> Element<Message> {
-        match self {
-            Self::Welcome => Self::welcome(),
-            Self::End {
-                theme,
+            Message::EditEntry(index) => match self {
+                Self::Skills {
+                    skills,
+                    selection,
+                    text,
+                    editing,
+                    ..
+                } => {
+                    let (skill, level) = &skills[index];
+                    *text = skill.clone();
+                    *selection = *level;
+                    *editing = Some(index);
+                }
+                Self::Education {
+                    education_history,
+                    school,
+                    field,
+                    degree,
+                    start_date,
+                    end_date,
+                    editing,
+                    ..
+                } => {
+                    let entry = &education_history[index];
+                    *school = entry.school.clone();
+                    *field = entry.field.clone().unwrap_or_default();
+                    *degree = entry.degree;
+                    *start_date = entry.start.to_string();
+                    *end_date = entry.end.to_string();
+                    *editing = Some(index);
+                }
+                Self::Work {
+                    work_history,
+                    company,
+                    position,
+                    description,
+                    start_date,
+                    end_date,
+                    highlights,
+                    editing,
+                    ..
+                } => {
+                    let entry = &work_history[index];
+                    *company = entry.company.clone();
+                    *position = entry.position.clone();
+                    *description = entry.description.clone();
+                    *highlights = entry.highlights.clone();
+                    *start_date = entry.start.to_string();
+                    *end_date = entry.end.to_string();
+                    *editing = Some(index);
+                }
+                Self::Projects {
+                    projects,
+                    name,
+                    url,
+                    repository,
+                    role,
+                    start_date,
+                    end_date,
+                    technologies,
+                    highlights,
+                    editing,
+                    ..
+                } => {
+                    let entry = &projects[index];
+                    *name = entry.name.clone();
+                    *url = entry.url.clone().unwrap_or_default();
+                    *repository = entry.repository.clone().unwrap_or_default();
+                    *role = entry.role.clone().unwrap_or_default();
+                    *start_date = entry.start.map(|d| d.to_string()).unwrap_or_default();
+                    *end_date = entry.end.map(|d| d.to_string()).unwrap_or_default();
+                    *technologies = entry.technologies.join(", ");
+                    *highlights = entry.highlights.clone();
+                    *editing = Some(index);
+                }
+                Self::Certifications {
+                    certifications,
+                    fields,
+                    editing,
+                    ..
+                } => {
+                    let entry = &certifications[index];
+                    fill_fields(
+                        fields,
+                        [
+                            entry.name.clone(),
+                            entry.issuer.clone(),
+                            entry.credential_id.clone().unwrap_or_default(),
+                            entry.issued.map(|d| d.to_string()).unwrap_or_default(),
+                            entry.expires.map(|d| d.to_string()).unwrap_or_default(),
+                            entry.url.clone().unwrap_or_default(),
+                        ],
+                    );
+                    *editing = Some(index);
+                }
+                Self::Awards {
+                    awards,
+                    fields,
+                    editing,
+                    ..
+                } => {
+                    let entry = &awards[index];
+                    fill_fields(
+                        fields,
+                        [
+                            entry.title.clone(),
+                            entry.issuer.clone().unwrap_or_default(),
+                            entry.date.map(|d| d.to_string()).unwrap_or_default(),
+                            entry.summary.clone().unwrap_or_default(),
+                        ],
+                    );
+                    *editing = Some(index);
+                }
+                Self::Publications {
+                    publications,
+                    fields,
+                    editing,
+                    ..
+                } => {
+                    let entry = &publications[index];
+                    fill_fields(
+                        fields,
+                        [
+                            entry.title.clone(),
+                            entry.authors.join(", "),
+                            entry.venue.clone().unwrap_or_default(),
+                            entry.year.map(|y| y.to_string()).unwrap_or_default(),
+                            entry.doi.clone().unwrap_or_default(),
+                            entry.url.clone().unwrap_or_default(),
+                        ],
+                    );
+                    *editing = Some(index);
+                }
+                Self::CustomSections {
+                    sections,
+                    title,
+                    entries,
+                    editing,
+                    ..
+                } => {
+                    let section = &sections[index];
+                    title.text = section.title.clone();
+                    *entries = section.entries.clone();
+                    *editing = Some(index);
+                    self.clear_custom_entry_inputs();
+                }
+                _ => unreachable!(),
+            },
+            Message::DeleteEntry(index) => match self {
+                Self::Skills {
+                    skills, editing, ..
+                } => delete_entry(skills, editing, index),
+                Self::Education {
+                    education_history,
+                    editing,
+                    ..
+                } => delete_entry(education_history, editing, index),
+                Self::Work {
+                    work_history,
+                    editing,
+                    ..
+                } => delete_entry(work_history, editing, index),
+                Self::Projects {
+                    projects, editing, ..
+                } => delete_entry(projects, editing, index),
+                Self::Certifications {
+                    certifications,
+                    editing,
+                    ..
+                } => delete_entry(certifications, editing, index),
+                Self::Awards {
+                    awards, editing, ..
+                } => delete_entry(awards, editing, index),
+                Self::Publications {
+                    publications,
+                    editing,
+                    ..
+                } => delete_entry(publications, editing, index),
+                Self::CustomSections {
+                    sections, editing, ..
+                } => delete_entry(sections, editing, index),
+                _ => unreachable!(),
+            },
+            Message::MoveEntryUp(index) => match self {
+                Self::Skills {
+                    skills, editing, ..
+                } => swap_entries(skills, editing, index - 1, index),
+                Self::Education {
+                    education_history,
+                    editing,
+                    ..
+                } => swap_entries(education_history, editing, index - 1, index),
+                Self::Work {
+                    work_history,
+                    editing,
+                    ..
+                } => swap_entries(work_history, editing, index - 1, index),
+                Self::Projects {
+                    projects, editing, ..
+                } => swap_entries(projects, editing, index - 1, index),
+                Self::Certifications {
+                    certifications,
+                    editing,
+                    ..
+                } => swap_entries(certifications, editing, index - 1, index),
+                Self::Awards {
+                    awards, editing, ..
+                } => swap_entries(awards, editing, index - 1, index),
+                Self::Publications {
+                    publications,
+                    editing,
+                    ..
+                } => swap_entries(publications, editing, index - 1, index),
+                Self::CustomSections {
+                    sections, editing, ..
+                } => swap_entries(sections, editing, index - 1, index),
+                _ => unreachable!(),
+            },
+            Message::MoveEntryDown(index) => match self {
+                Self::Skills {
+                    skills, editing, ..
+                } => swap_entries(skills, editing, index, index + 1),
+                Self::Education {
+                    education_history,
+                    editing,
+                    ..
+                } => swap_entries(education_history, editing, index, index + 1),
+                Self::Work {
+                    work_history,
+                    editing,
+                    ..
+                } => swap_entries(work_history, editing, index, index + 1),
+                Self::Projects {
+                    projects, editing, ..
+                } => swap_entries(projects, editing, index, index + 1),
+                Self::Certifications {
+                    certifications,
+                    editing,
+                    ..
+                } => swap_entries(certifications, editing, index, index + 1),
+                Self::Awards {
+                    awards, editing, ..
+                } => swap_entries(awards, editing, index, index + 1),
+                Self::Publications {
+                    publications,
+                    editing,
+                    ..
+                } => swap_entries(publications, editing, index, index + 1),
+                Self::CustomSections {
+                    sections, editing, ..
+                } => swap_entries(sections, editing, index, index + 1),
+                _ => unreachable!(),
+            },
+            Message::NextPressed | Message::BackPressed | Message::ImportFile => {}
+        }
+    }
+
+    // Empty the inputs of the Projects step, leaving its list of projects alone
+    fn clear_project_inputs(&mut self) {
+        if let Self::Projects {
+            name,
+            name_state,
+            url,
+            url_state,
+            repository,
+            repository_state,
+            role,
+            role_state,
+            start_date,
+            start_date_state,
+            end_date,
+            end_date_state,
+            technologies,
+            technologies_state,
+            highlights,
+            highlight,
+            highlight_state,
+            ..
+        } = self
+        {
+            for text in [
+                name,
+                url,
+                repository,
+                role,
+                start_date,
+                end_date,
+                technologies,
+                highlight,
+            ] {
+                *text = String::new();
+            }
+            for state in [
+                name_state,
+                url_state,
+                repository_state,
+                role_state,
+                start_date_state,
+                end_date_state,
+                technologies_state,
+                highlight_state,
+            ] {
+                *state = text_input::State::new();
+            }
+            *highlights = Vec::new();
+        }
+    }
+
+    // The fields of the current step's form, for steps that have one
+    fn fields(&mut self) -> Option<&mut [Field]> {
+        match self {
+            Self::Certifications { fields, .. } | Self::Publications { fields, .. } => Some(fields),
+            Self::Awards { fields, .. } => Some(fields),
+            Self::CustomSections { fields, .. } => Some(fields),
+            _ => None,
+        }
+    }
+
+    fn clear_fields(&mut self) {
+        if let Some(fields) = self.fields() {
+            for field in fields {
+                *field = Field::default();
+            }
+        }
+    }
+
+    // Empty the inputs for an entry of the Custom Sections step
+    fn clear_custom_entry_inputs(&mut self) {
+        self.clear_fields();
+        if let Self::CustomSections {
+            highlights,
+            highlight,
+            highlight_state,
+            ..
+        } = self
+        {
+            *highlights = Vec::new();
+            *highlight = String::new();
+            *highlight_state = text_input::State::new();
+        }
+    }
+
+    fn title(&self) -> &str {
+        match self {
+            Self::Welcome { .. } => "Welcome",
+            Self::Name { .. } => "Name",
+            Self::Profession { .. } => "Profession",
+            Self::ContactInfo { .. } => "Contact Information",
+            Self::Description { .. } => "Description",
+            Self::Skills { .. } => "Skills",
+            Self::Education { .. } => "Education",
+            Self::Work { .. } => "Work",
+            Self::Projects { .. } => "Projects",
+            Self::Certifications { .. } => "Certifications",
+            Self::Awards { .. } => "Awards",
+            Self::Publications { .. } => "Publications",
+            Self::CustomSections { .. } => "Custom Sections",
+            Self::End { .. } => "End",
+        }
+    }
+
+    fn can_continue(&self) -> bool {
+        match self {
+            Self::Welcome { .. } | Self::Skills { .. } => true,
+            Self::ContactInfo { info, .. } => info.problems().is_empty(),
+            Self::Name {
+                first_name,
+                last_name,
+                ..
+            } => !first_name.is_empty() && !last_name.is_empty(),
+            Self::Profession { text, .. } | Self::Description { text, .. } => !text.is_empty(),
+            Self::Education { .. } => true,
+            Self::Work { .. } => true,
+            Self::Projects { .. }
+            | Self::Certifications { .. }
+            | Self::Awards { .. }
+            | Self::Publications { .. }
+            | Self::CustomSections { .. } => true,
+            Self::End { .. } => false,
+        }
+    }
+
+    fn view(&mut self, app: &App) -> Element<'_, Message> {
+        match self {
+            Self::Welcome {
+                import_file,
+                import_file_state,
+                import_button_state,
+                status,
+            } => Self::welcome(import_file, import_file_state, import_button_state, status),
+            Self::End {
+                template,
+                theme,
                 save_file,
                 save_file_state,
                 button_state,
-            } => Self::end(Some(*theme), save_file, save_file_state, button_state, app),
+                offline,
+                structured_data,
+                fix_contrast,
+                layout_controls,
+                status,
+                ..
+            } => {
+                let sections = app.steps.to_resume().arrangement();
+                layout_controls.resize_with(sections.len(), Default::default);
+                Self::end(
+                    Some(*template),
+                    Some(*theme),
+                    *offline,
+                    *structured_data,
+                    *fix_contrast,
+                    sections,
+                    layout_controls,
+                    save_file,
+                    save_file_state,
+                    button_state,
+                    status.as_deref(),
+                    app,
+                )
+            }
             Self::Name {
                 first_name,
                 last_name,
@@ -787,6 +2083,21 @@ impl<'a> Step {
                 last_name_state,
             } => Self::name(first_name, first_name_state, last_name, last_name_state),
             Self::Profession { text, state } => Self::profession(text, state),
+            Self::ContactInfo {
+                info,
+                email_state,
+                phone_state,
+                website_state,
+                github_state,
+                linkedin_state,
+            } => Self::contact_info(
+                info,
+                email_state,
+                phone_state,
+                website_state,
+                github_state,
+                linkedin_state,
+            ),
             Self::Description { text, state } => Self::description(text, state),
             Self::Skills {
                 skills,
@@ -795,14 +2106,21 @@ impl<'a> Step {
                 text_state,
                 button_state,
                 clear_state,
-            } => Self::skills(
-                &skills,
-                text,
-                *selection,
-                text_state,
-                button_state,
-                clear_state,
-            ),
+                editing,
+                controls,
+            } => {
+                controls.resize_with(skills.len(), Default::default);
+                Self::skills(
+                    skills,
+                    text,
+                    *selection,
+                    text_state,
+                    button_state,
+                    clear_state,
+                    *editing,
+                    controls,
+                )
+            }
             Self::Education {
                 education_history,
 
@@ -810,29 +2128,38 @@ impl<'a> Step {
 
                 field,
                 field_state,
-                start_year,
-                start_year_state,
-                end_year,
-                end_year_state,
+                start_date,
+                start_date_state,
+                end_date,
+                end_date_state,
                 school,
                 school_state,
 
                 button_state,
                 clear_state,
-            } => Self::education(
-                education_history,
-                *degree,
-                field,
-                field_state,
-                start_year,
-                start_year_state,
-                end_year,
-                end_year_state,
-                school,
-                school_state,
-                button_state,
-                clear_state,
-            ),
+                editing,
+                controls,
+                status,
+            } => {
+                controls.resize_with(education_history.len(), Default::default);
+                let column = Self::education(
+                    education_history,
+                    *degree,
+                    field,
+                    field_state,
+                    start_date,
+                    start_date_state,
+                    end_date,
+                    end_date_state,
+                    school,
+                    school_state,
+                    button_state,
+                    clear_state,
+                    *editing,
+                    controls,
+                );
+                Self::with_status(column, status)
+            }
             Self::Work {
                 work_history,
                 company,
@@ -841,28 +2168,262 @@ impl<'a> Step {
                 position_state,
                 description,
                 description_state,
-                start_year,
-                start_year_state,
-                end_year,
-                end_year_state,
+                start_date,
+                start_date_state,
+                end_date,
+                end_date_state,
+                highlights,
+                highlight,
+                highlight_state,
+                highlight_button_state,
+                highlight_controls,
                 button_state,
                 clear_state,
-            } => Self::work(
-                work_history,
-                company,
-                company_state,
-                position,
-                position_state,
-                description,
-                description_state,
-                start_year,
-                start_year_state,
-                end_year,
-                end_year_state,
+                editing,
+                controls,
+                status,
+            } => {
+                controls.resize_with(work_history.len(), Default::default);
+                highlight_controls.resize_with(highlights.len(), Default::default);
+                let column = Self::work(
+                    work_history,
+                    company,
+                    company_state,
+                    position,
+                    position_state,
+                    description,
+                    description_state,
+                    start_date,
+                    start_date_state,
+                    end_date,
+                    end_date_state,
+                    Self::highlights(
+                        highlights,
+                        highlight,
+                        highlight_state,
+                        highlight_button_state,
+                        highlight_controls,
+                    ),
+                    button_state,
+                    clear_state,
+                    *editing,
+                    controls,
+                );
+                Self::with_status(column, status)
+            }
+            Self::Projects {
+                projects,
+                name,
+                name_state,
+                url,
+                url_state,
+                repository,
+                repository_state,
+                role,
+                role_state,
+                start_date,
+                start_date_state,
+                end_date,
+                end_date_state,
+                technologies,
+                technologies_state,
+                highlights,
+                highlight,
+                highlight_state,
+                highlight_button_state,
+                highlight_controls,
                 button_state,
                 clear_state,
-            ),
-            _ => unreachable!(),
+                editing,
+                controls,
+                status,
+            } => {
+                controls.resize_with(projects.len(), Default::default);
+                highlight_controls.resize_with(highlights.len(), Default::default);
+                let column = Self::projects(
+                    projects,
+                    [
+                        (name, name_state),
+                        (url, url_state),
+                        (repository, repository_state),
+                        (role, role_state),
+                        (start_date, start_date_state),
+                        (end_date, end_date_state),
+                        (technologies, technologies_state),
+                    ],
+                    Self::highlights(
+                        highlights,
+                        highlight,
+                        highlight_state,
+                        highlight_button_state,
+                        highlight_controls,
+                    ),
+                    button_state,
+                    clear_state,
+                    *editing,
+                    controls,
+                );
+                Self::with_status(column, status)
+            }
+            Self::Certifications {
+                certifications,
+                fields,
+                button_state,
+                clear_state,
+                editing,
+                controls,
+                status,
+            } => {
+                controls.resize_with(certifications.len(), Default::default);
+                let problem = read_certification(fields).err();
+                let column = Self::form(
+                    "Certifications",
+                    certifications
+                        .iter()
+                        .map(|c| {
+                            if c.is_expired() {
+                                format!("{}, {} (expired)", c.name, c.issuer)
+                            } else {
+                                format!("{}, {}", c.name, c.issuer)
+                            }
+                        })
+                        .collect(),
+                    fields,
+                    &[
+                        (
+                            "What is the certification called?",
+                            "Type something to continue",
+                        ),
+                        ("Who issued it?", "Type something to continue"),
+                        ("What is its credential ID? (optional)", "ABC-123"),
+                        ("When was it issued? (optional)", "Mar 2021"),
+                        ("When does it expire? (optional)", "Mar 2024"),
+                        (
+                            "Where can it be verified? (optional)",
+                            "https://example.com/verify",
+                        ),
+                    ],
+                    problem,
+                    ("Certification", Message::AddCertification),
+                    button_state,
+                    clear_state,
+                    *editing,
+                    controls,
+                );
+                Self::with_status(column, status)
+            }
+            Self::Awards {
+                awards,
+                fields,
+                button_state,
+                clear_state,
+                editing,
+                controls,
+                status,
+            } => {
+                controls.resize_with(awards.len(), Default::default);
+                let problem = read_award(fields).err();
+                let column = Self::form(
+                    "Awards",
+                    awards.iter().map(|a| a.title.clone()).collect(),
+                    fields,
+                    &[
+                        ("What award did you receive?", "Type something to continue"),
+                        ("Who awarded it? (optional)", "ACM"),
+                        ("When? (optional)", "2021"),
+                        ("What was it for? (optional)", "Describe the award"),
+                    ],
+                    problem,
+                    ("Award", Message::AddAward),
+                    button_state,
+                    clear_state,
+                    *editing,
+                    controls,
+                );
+                Self::with_status(column, status)
+            }
+            Self::Publications {
+                publications,
+                fields,
+                button_state,
+                clear_state,
+                editing,
+                controls,
+                status,
+            } => {
+                controls.resize_with(publications.len(), Default::default);
+                let problem = read_publication(fields).err();
+                let column = Self::form(
+                    "Publications",
+                    publications.iter().map(|p| p.title.clone()).collect(),
+                    fields,
+                    &[
+                        ("What is the title?", "Type something to continue"),
+                        (
+                            "Who are the authors? Separate them with commas. (optional)",
+                            "Ada Lovelace, Charles Babbage",
+                        ),
+                        ("Where was it published? (optional)", "Scientific Memoirs"),
+                        ("In what year? (optional)", "1843"),
+                        ("What is its DOI? (optional)", "10.1000/182"),
+                        (
+                            "Where can it be read? (optional)",
+                            "https://example.com/paper",
+                        ),
+                    ],
+                    problem,
+                    ("Publication", Message::AddPublication),
+                    button_state,
+                    clear_state,
+                    *editing,
+                    controls,
+                );
+                Self::with_status(column, status)
+            }
+            Self::CustomSections {
+                sections,
+                title,
+                entries,
+                entry_controls,
+                fields,
+                highlights,
+                highlight,
+                highlight_state,
+                highlight_button_state,
+                highlight_controls,
+                entry_button_state,
+                button_state,
+                clear_state,
+                editing,
+                controls,
+                status,
+            } => {
+                controls.resize_with(sections.len(), Default::default);
+                entry_controls.resize_with(entries.len(), Default::default);
+                highlight_controls.resize_with(highlights.len(), Default::default);
+                let problem = read_custom_entry(fields, highlights).err();
+                let column = Self::custom_sections(
+                    sections,
+                    title,
+                    entries,
+                    entry_controls,
+                    fields,
+                    problem,
+                    Self::highlights(
+                        highlights,
+                        highlight,
+                        highlight_state,
+                        highlight_button_state,
+                        highlight_controls,
+                    ),
+                    entry_button_state,
+                    button_state,
+                    clear_state,
+                    *editing,
+                    controls,
+                );
+                Self::with_status(column, status)
+            }
         }
         .into()
     }
@@ -871,13 +2432,85 @@ impl<'a> Step {
         Column::new().spacing(20).push(Text::new(title).size(50))
     }
 
+    // A step's view, followed by why its last entry could not be added
+    fn with_status(column: Column<'a, Message>, status: &Option<String>) -> Column<'a, Message> {
+        match status {
+            Some(status) => column.push(Text::new(status)),
+            None => column,
+        }
+    }
+
+    // The entries added so far, each with buttons to edit, move or delete it
+    fn entries(
+        labels: Vec<String>,
+        editing: Option<usize>,
+        controls: &'a mut [EntryControls],
+    ) -> Column<'a, Message> {
+        let last = labels.len().saturating_sub(1);
+        labels.into_iter().zip(controls).enumerate().fold(
+            Column::new().spacing(5),
+            |items, (i, (label, controls))| {
+                let mut up = small_button(&mut controls.up, "Up");
+                if i > 0 {
+                    up = up.on_press(Message::MoveEntryUp(i));
+                }
+                let mut down = small_button(&mut controls.down, "Down");
+                if i < last {
+                    down = down.on_press(Message::MoveEntryDown(i));
+                }
+                let label = if editing == Some(i) {
+                    format!("{} (editing)", label)
+                } else {
+                    label
+                };
+
+                items.push(
+                    Row::new()
+                        .spacing(5)
+                        .align_items(Align::Center)
+                        .push(Text::new(label).width(Length::Fill))
+                        .push(
+                            small_button(&mut controls.edit, "Edit")
+                                .on_press(Message::EditEntry(i)),
+                        )
+                        .push(up)
+                        .push(down)
+                        .push(
+                            small_button(&mut controls.delete, "Delete")
+                                .on_press(Message::DeleteEntry(i)),
+                        ),
+                )
+            },
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn end(
+        template: Option<TemplateOption>,
         selection: Option<ThemeOption>,
+        offline: bool,
+        structured_data: bool,
+        fix_contrast: bool,
+        sections: Vec<LayoutEntry>,
+        layout_controls: &'a mut [(button::State, button::State)],
         save_file: &str,
         save_file_state: &'a mut text_input::State,
         button_state: &'a mut button::State,
-        app: &App,
+        status: Option<&str>,
+        _app: &App,
     ) -> Column<'a, Message> {
+        let template_input = Column::new().push(TemplateOption::all().iter().cloned().fold(
+            Column::new().padding(10).spacing(20),
+            |choices, option| {
+                choices.push(Radio::new(
+                    option,
+                    option,
+                    template,
+                    Message::TemplateSelected,
+                ))
+            },
+        ));
+
         let theme_input = Column::new().push(ThemeOption::all().iter().cloned().fold(
             Column::new().padding(10).spacing(20),
             |choices, option| {
@@ -890,6 +2523,53 @@ impl<'a> Step {
             },
         ));
 
+        // Warn about colors of the chosen theme that are hard to read
+        let issues = selection
+            .map(|option| option.get_theme().contrast_issues())
+            .unwrap_or_default();
+        let contrast_input = issues
+            .iter()
+            .fold(Column::new().spacing(5), |warnings, issue| {
+                warnings.push(Text::new(format!("Hard to read: {}", issue)))
+            });
+        let contrast_input = if issues.is_empty() {
+            contrast_input
+        } else {
+            contrast_input.push(Checkbox::new(
+                fix_contrast,
+                "Adjust these colors so they are easier to read",
+                Message::FixContrastToggled,
+            ))
+        };
+
+        let last = sections.len().saturating_sub(1);
+        let layout_input = sections.into_iter().zip(layout_controls).enumerate().fold(
+            Column::new().padding(10).spacing(5),
+            |rows, (i, (entry, (up_state, down_state)))| {
+                let mut up = small_button(up_state, "Up");
+                if i > 0 {
+                    up = up.on_press(Message::MoveSectionUp(i));
+                }
+                let mut down = small_button(down_state, "Down");
+                if i < last {
+                    down = down.on_press(Message::MoveSectionDown(i));
+                }
+                rows.push(
+                    Row::new()
+                        .spacing(5)
+                        .align_items(Align::Center)
+                        .push(
+                            Checkbox::new(entry.visible, entry.section.title(), move |visible| {
+                                Message::SectionToggled(i, visible)
+                            })
+                            .width(Length::Fill),
+                        )
+                        .push(up)
+                        .push(down),
+                )
+            },
+        );
+
         let text_input = TextInput::new(
             save_file_state,
             "Type something",
@@ -900,20 +2580,45 @@ impl<'a> Step {
         .width(Length::Fill)
         .size(30);
 
-        Self::container("End")
+        let result = Self::container("End")
+            .push(Text::new("How should your resume be laid out?"))
+            .push(template_input)
             .push(Text::new("What theme would you like your resume to have?"))
             .push(theme_input)
-            .push(Text::new("Where do you want to save your resume?"))
+            .push(contrast_input)
+            .push(Checkbox::new(
+                offline,
+                "Make the page work without an internet connection",
+                Message::OfflineToggled,
+            ))
+            .push(Checkbox::new(
+                structured_data,
+                "Let search engines read your name, job and contact details from the page",
+                Message::StructuredDataToggled,
+            ))
+            .push(Text::new(
+                "Which sections should your resume show, and in what order?",
+            ))
+            .push(layout_input)
+            .push(Text::new(
+                "Where do you want to save your resume? Use a .pdf file for a PDF, a .docx file for a Word document, a .odt file for LibreOffice, a .tex file for LaTeX, a .md or .txt file for plain text, or any other for a webpage.",
+            ))
             .push(text_input)
             .push(
                 button(button_state, "Save")
                     .on_press(Message::SaveFile)
                     .style(style::Button::Primary),
-            )
+            );
+
+        match status {
+            Some(status) => result.push(Text::new(status)),
+            None => result,
+        }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn work(
-        work_history: &Vec<Work>,
+        work_history: &[Work],
 
         company: &str,
         company_state: &'a mut text_input::State,
@@ -922,13 +2627,17 @@ impl<'a> Step {
         description: &str,
         description_state: &'a mut text_input::State,
 
-        start_year: &str,
-        start_year_state: &'a mut text_input::State,
-        end_year: &str,
-        end_year_state: &'a mut text_input::State,
+        start_date: &str,
+        start_date_state: &'a mut text_input::State,
+        end_date: &str,
+        end_date_state: &'a mut text_input::State,
+
+        highlights: Column<'a, Message>,
 
         button_state: &'a mut button::State,
         clear_state: &'a mut button::State,
+        editing: Option<usize>,
+        controls: &'a mut [EntryControls],
     ) -> Column<'a, Message> {
         let company_input = TextInput::new(
             company_state,
@@ -965,10 +2674,10 @@ impl<'a> Step {
             .spacing(10)
             .push(
                 TextInput::new(
-                    start_year_state,
-                    "Start Year",
-                    start_year,
-                    Message::StartYearChanged,
+                    start_date_state,
+                    "Start, like Mar 2021",
+                    start_date,
+                    Message::StartDateChanged,
                 )
                 .padding(10)
                 .width(Length::Fill)
@@ -977,10 +2686,10 @@ impl<'a> Step {
             .push(Text::new("to"))
             .push(
                 TextInput::new(
-                    end_year_state,
-                    "End Year",
-                    end_year,
-                    Message::EndYearChanged,
+                    end_date_state,
+                    "End, or present",
+                    end_date,
+                    Message::EndDateChanged,
                 )
                 .padding(10)
                 .width(Length::Fill)
@@ -988,72 +2697,428 @@ impl<'a> Step {
             );
 
         let result = Self::container("Work History")
-            .push(
+            .push(Self::entries(
                 work_history
                     .iter()
-                    .cloned()
-                    .fold(Column::new(), |items, e| {
-                        items.push(Text::new(format!("{} at {}", e.position, e.company)))
-                    }),
-            )
+                    .map(|e| format!("{} at {}", e.position, e.company))
+                    .collect(),
+                editing,
+                controls,
+            ))
             .push(Text::new("Where did you work?"))
             .push(company_input)
-            .push(Text::new("Between which years were you employed?"))
+            .push(Text::new(
+                "When were you employed? Give a year, a month like Mar 2021, or present.",
+            ))
             .push(years_input)
+            .push(Text::new(
+                date_problem(start_date, end_date).unwrap_or_default(),
+            ))
             .push(Text::new("What was your position?"))
             .push(position_input)
-            .push(Text::new("Describe your work"))
-            .push(description_input);
-
-        if !position.is_empty()
-            && !company.is_empty()
-            && !description.is_empty()
-            && start_year
-                .parse::<u32>()
-                .and(end_year.parse::<u32>())
-                .is_ok()
+            .push(Text::new("Summarize your work in a line (optional)"))
+            .push(description_input)
+            .push(Text::new("What did you achieve?"))
+            .push(highlights);
+
+        if !position.is_empty() && !company.is_empty() && parse_range(start_date, end_date).is_ok()
         {
             result.push(
                 Row::new()
                     .push(
-                        button(clear_state, "Clear")
-                            .on_press(Message::Clear)
-                            .style(style::Button::Secondary),
+                        button(
+                            clear_state,
+                            if editing.is_some() { "Cancel" } else { "Clear" },
+                        )
+                        .on_press(Message::Clear)
+                        .style(style::Button::Secondary),
                     )
                     .push(Space::with_width(Length::Fill))
                     .push(
-                        button(button_state, "Add Work")
-                            .on_press(Message::AddWork)
-                            .style(style::Button::Primary),
+                        button(
+                            button_state,
+                            if editing.is_some() {
+                                "Save Work"
+                            } else {
+                                "Add Work"
+                            },
+                        )
+                        .on_press(Message::AddWork)
+                        .style(style::Button::Primary),
                     ),
             )
         } else {
             result.push(
                 Row::new().push(
-                    button(clear_state, "Clear")
-                        .on_press(Message::Clear)
-                        .style(style::Button::Secondary),
+                    button(
+                        clear_state,
+                        if editing.is_some() { "Cancel" } else { "Clear" },
+                    )
+                    .on_press(Message::Clear)
+                    .style(style::Button::Secondary),
                 ),
             )
         }
     }
 
+    fn projects(
+        projects: &[Project],
+        // The text and state of each input, in the order they are shown
+        inputs: [(&str, &'a mut text_input::State); 7],
+        highlights: Column<'a, Message>,
+        button_state: &'a mut button::State,
+        clear_state: &'a mut button::State,
+        editing: Option<usize>,
+        controls: &'a mut [EntryControls],
+    ) -> Column<'a, Message> {
+        let [name, url, repository, role, start_date, end_date, technologies] = inputs;
+        let can_add = !name.0.is_empty() && parse_optional_range(start_date.0, end_date.0).is_ok();
+        let dates_problem = parse_optional_range(start_date.0, end_date.0).err();
+
+        let input = |(text, state): (&str, &'a mut text_input::State),
+                     placeholder: &str,
+                     message: fn(String) -> Message| {
+            TextInput::new(state, placeholder, text, message)
+                .padding(10)
+                .width(Length::Fill)
+                .size(20)
+        };
+
+        let dates_input = Row::new()
+            .spacing(10)
+            .push(input(
+                start_date,
+                "Start, like Mar 2021",
+                Message::StartDateChanged,
+            ))
+            .push(Text::new("to"))
+            .push(input(end_date, "End, or present", Message::EndDateChanged));
+
+        let result = Self::container("Projects")
+            .push(Self::entries(
+                projects
+                    .iter()
+                    .map(|p| match &p.role {
+                        Some(role) => format!("{}, {}", p.name, role),
+                        None => p.name.clone(),
+                    })
+                    .collect(),
+                editing,
+                controls,
+            ))
+            .push(Text::new("What is the project called?"))
+            .push(input(
+                name,
+                "Type something to continue",
+                Message::ProjectNameChanged,
+            ))
+            .push(Text::new(
+                "Where can it be seen, and where is its source code? (optional)",
+            ))
+            .push(input(url, "https://example.com", Message::UrlChanged))
+            .push(input(
+                repository,
+                "https://github.com/you/project",
+                Message::RepositoryChanged,
+            ))
+            .push(Text::new("What was your role? (optional)"))
+            .push(input(role, "Maintainer", Message::RoleChanged))
+            .push(Text::new("When did you work on it? (optional)"))
+            .push(dates_input)
+            .push(Text::new(dates_problem.unwrap_or_default()))
+            .push(Text::new(
+                "Which technologies did it use? Separate them with commas.",
+            ))
+            .push(input(
+                technologies,
+                "Rust, WebAssembly",
+                Message::TechnologiesChanged,
+            ))
+            .push(Text::new("What are its highlights?"))
+            .push(highlights);
+
+        let clear = button(
+            clear_state,
+            if editing.is_some() { "Cancel" } else { "Clear" },
+        )
+        .on_press(Message::Clear)
+        .style(style::Button::Secondary);
+        if can_add {
+            result.push(
+                Row::new()
+                    .push(clear)
+                    .push(Space::with_width(Length::Fill))
+                    .push(
+                        button(
+                            button_state,
+                            if editing.is_some() {
+                                "Save Project"
+                            } else {
+                                "Add Project"
+                            },
+                        )
+                        .on_press(Message::AddProject)
+                        .style(style::Button::Primary),
+                    ),
+            )
+        } else {
+            result.push(Row::new().push(clear))
+        }
+    }
+
+    // The highlights of an entry, each with a button to remove it, and an input to add another
+    fn highlights(
+        highlights: &[String],
+        highlight: &str,
+        highlight_state: &'a mut text_input::State,
+        highlight_button_state: &'a mut button::State,
+        highlight_controls: &'a mut [button::State],
+    ) -> Column<'a, Message> {
+        let mut input = Row::new().spacing(10).push(
+            TextInput::new(
+                highlight_state,
+                "Something you are proud of",
+                highlight,
+                Message::HighlightChanged,
+            )
+            .on_submit(Message::AddHighlight)
+            .padding(10)
+            .width(Length::Fill)
+            .size(20),
+        );
+        if !highlight.trim().is_empty() {
+            input = input.push(
+                button(highlight_button_state, "Add")
+                    .on_press(Message::AddHighlight)
+                    .style(style::Button::Secondary),
+            );
+        }
+
+        highlights
+            .iter()
+            .zip(highlight_controls)
+            .enumerate()
+            .fold(Column::new().spacing(5), |items, (i, (h, remove))| {
+                items.push(
+                    Row::new()
+                        .spacing(5)
+                        .align_items(Align::Center)
+                        .push(Text::new(format!("• {}", h)).width(Length::Fill))
+                        .push(small_button(remove, "Remove").on_press(Message::RemoveHighlight(i))),
+                )
+            })
+            .push(input)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn custom_sections(
+        sections: &[CustomSection],
+        title: &'a mut Field,
+        entries: &[CustomEntry],
+        entry_controls: &'a mut [button::State],
+        fields: &'a mut [Field],
+        // Why the entry can not be added yet, if it can not
+        problem: Option<String>,
+        bullets: Column<'a, Message>,
+        entry_button_state: &'a mut button::State,
+        button_state: &'a mut button::State,
+        clear_state: &'a mut button::State,
+        editing: Option<usize>,
+        controls: &'a mut [EntryControls],
+    ) -> Column<'a, Message> {
+        let explanation = if fields[0].text.is_empty() {
+            String::new()
+        } else {
+            problem.clone().unwrap_or_default()
+        };
+        let can_add_section = !title.text.trim().is_empty() && !entries.is_empty();
+
+        let result = Self::container("Custom Sections")
+            .push(Text::new(
+                "Add any section your resume needs, like volunteering or languages.",
+            ))
+            .push(Self::entries(
+                sections
+                    .iter()
+                    .map(|s| match s.entries.len() {
+                        1 => format!("{} (1 entry)", s.title),
+                        n => format!("{} ({} entries)", s.title, n),
+                    })
+                    .collect(),
+                editing,
+                controls,
+            ))
+            .push(Text::new("What is the section called?"))
+            .push(
+                TextInput::new(
+                    &mut title.state,
+                    "Volunteering",
+                    &title.text,
+                    Message::SectionTitleChanged,
+                )
+                .padding(10)
+                .width(Length::Fill)
+                .size(30),
+            )
+            .push(entries.iter().zip(entry_controls).enumerate().fold(
+                Column::new().spacing(5),
+                |items, (i, (entry, remove))| {
+                    items.push(
+                        Row::new()
+                            .spacing(5)
+                            .align_items(Align::Center)
+                            .push(Text::new(format!("• {}", entry.heading)).width(Length::Fill))
+                            .push(
+                                small_button(remove, "Remove")
+                                    .on_press(Message::RemoveCustomEntry(i)),
+                            ),
+                    )
+                },
+            ));
+
+        let result = fields
+            .iter_mut()
+            .zip(&[
+                ("What is the entry's heading?", "Mentor"),
+                ("What is its subheading? (optional)", "Code Club"),
+                ("When did it start? (optional)", "Mar 2021"),
+                ("When did it end? (optional)", "present"),
+                ("Where was it? (optional)", "London"),
+            ])
+            .enumerate()
+            .fold(result, |result, (i, (field, (question, placeholder)))| {
+                result.push(Text::new(*question)).push(
+                    TextInput::new(&mut field.state, placeholder, &field.text, move |text| {
+                        Message::FieldChanged(i, text)
+                    })
+                    .padding(10)
+                    .width(Length::Fill)
+                    .size(20),
+                )
+            })
+            .push(Text::new("What should be listed under it?"))
+            .push(bullets)
+            .push(Text::new(explanation));
+
+        let mut add_entry = button(entry_button_state, "Add Entry").style(style::Button::Secondary);
+        if problem.is_none() {
+            add_entry = add_entry.on_press(Message::AddCustomEntry);
+        }
+        let result = result.push(add_entry);
+
+        let clear = button(
+            clear_state,
+            if editing.is_some() { "Cancel" } else { "Clear" },
+        )
+        .on_press(Message::Clear)
+        .style(style::Button::Secondary);
+        if can_add_section {
+            result.push(
+                Row::new()
+                    .push(clear)
+                    .push(Space::with_width(Length::Fill))
+                    .push(
+                        button(
+                            button_state,
+                            if editing.is_some() {
+                                "Save Section"
+                            } else {
+                                "Add Section"
+                            },
+                        )
+                        .on_press(Message::AddCustomSection)
+                        .style(style::Button::Primary),
+                    ),
+            )
+        } else {
+            result.push(Row::new().push(clear))
+        }
+    }
+
+    // A list of entries with one text input for each of their details
+    #[allow(clippy::too_many_arguments)]
+    fn form(
+        title: &str,
+        labels: Vec<String>,
+        fields: &'a mut [Field],
+        // The question and placeholder of each field
+        questions: &[(&str, &str)],
+        // Why the inputs can not be added yet, if they can not
+        problem: Option<String>,
+        (noun, add): (&str, Message),
+        button_state: &'a mut button::State,
+        clear_state: &'a mut button::State,
+        editing: Option<usize>,
+        controls: &'a mut [EntryControls],
+    ) -> Column<'a, Message> {
+        // The first field is required, so there is nothing to explain until it is typed in
+        let explanation = if fields[0].text.is_empty() {
+            String::new()
+        } else {
+            problem.clone().unwrap_or_default()
+        };
+
+        let result = fields.iter_mut().zip(questions).enumerate().fold(
+            Self::container(title).push(Self::entries(labels, editing, controls)),
+            |result, (i, (field, (question, placeholder)))| {
+                result.push(Text::new(*question)).push(
+                    TextInput::new(&mut field.state, placeholder, &field.text, move |text| {
+                        Message::FieldChanged(i, text)
+                    })
+                    .padding(10)
+                    .width(Length::Fill)
+                    .size(20),
+                )
+            },
+        );
+        let result = result.push(Text::new(explanation));
+
+        let clear = button(
+            clear_state,
+            if editing.is_some() { "Cancel" } else { "Clear" },
+        )
+        .on_press(Message::Clear)
+        .style(style::Button::Secondary);
+        if problem.is_none() {
+            let label = if editing.is_some() {
+                format!("Save {}", noun)
+            } else {
+                format!("Add {}", noun)
+            };
+            result.push(
+                Row::new()
+                    .push(clear)
+                    .push(Space::with_width(Length::Fill))
+                    .push(
+                        button(button_state, &label)
+                            .on_press(add)
+                            .style(style::Button::Primary),
+                    ),
+            )
+        } else {
+            result.push(Row::new().push(clear))
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn education(
-        education_history: &Vec<Education>,
+        education_history: &[Education],
 
         degree: Option<Degree>,
 
         field: &str,
         field_state: &'a mut text_input::State,
-        start_year: &str,
-        start_year_state: &'a mut text_input::State,
-        end_year: &str,
-        end_year_state: &'a mut text_input::State,
+        start_date: &str,
+        start_date_state: &'a mut text_input::State,
+        end_date: &str,
+        end_date_state: &'a mut text_input::State,
         school: &str,
         school_state: &'a mut text_input::State,
 
         button_state: &'a mut button::State,
         clear_state: &'a mut button::State,
+        editing: Option<usize>,
+        controls: &'a mut [EntryControls],
     ) -> Column<'a, Message> {
         let school_input = TextInput::new(
             school_state,
@@ -1080,10 +3145,10 @@ impl<'a> Step {
             .spacing(10)
             .push(
                 TextInput::new(
-                    start_year_state,
-                    "Start Year",
-                    start_year,
-                    Message::StartYearChanged,
+                    start_date_state,
+                    "Start, like Mar 2021",
+                    start_date,
+                    Message::StartDateChanged,
                 )
                 .padding(10)
                 .width(Length::Fill)
@@ -1092,10 +3157,10 @@ impl<'a> Step {
             .push(Text::new("to"))
             .push(
                 TextInput::new(
-                    end_year_state,
-                    "End Year",
-                    end_year,
-                    Message::EndYearChanged,
+                    end_date_state,
+                    "End, or present",
+                    end_date,
+                    Message::EndDateChanged,
                 )
                 .padding(10)
                 .width(Length::Fill)
@@ -1113,61 +3178,77 @@ impl<'a> Step {
                 ));
 
         let result = Self::container("Education History")
-            .push(
+            .push(Self::entries(
                 education_history
                     .iter()
-                    .cloned()
-                    .fold(Column::new(), |items, e| {
-                        items.push(Text::new(format!("Attended {}", e.school)))
-                    }),
-            )
+                    .map(|e| format!("Attended {}", e.school))
+                    .collect(),
+                editing,
+                controls,
+            ))
             .push(Text::new("Which school did you attend?"))
             .push(school_input)
-            .push(Text::new("Between which years did you attend?"))
+            .push(Text::new(
+                "When did you attend? Give a year, a month like Mar 2021, or present.",
+            ))
             .push(years_input)
+            .push(Text::new(
+                date_problem(start_date, end_date).unwrap_or_default(),
+            ))
             .push(Text::new("What was your field of study?"))
             .push(field_input)
             .push(Text::new("What degree did you receive?"))
             .push(degree_input);
 
-        if !school.is_empty()
-            && start_year
-                .parse::<u32>()
-                .and(end_year.parse::<u32>())
-                .is_ok()
-        {
+        if !school.is_empty() && parse_range(start_date, end_date).is_ok() {
             result.push(
                 Row::new()
                     .push(
-                        button(clear_state, "Clear")
-                            .on_press(Message::Clear)
-                            .style(style::Button::Secondary),
+                        button(
+                            clear_state,
+                            if editing.is_some() { "Cancel" } else { "Clear" },
+                        )
+                        .on_press(Message::Clear)
+                        .style(style::Button::Secondary),
                     )
                     .push(Space::with_width(Length::Fill))
                     .push(
-                        button(button_state, "Add Education")
-                            .on_press(Message::AddEducation)
-                            .style(style::Button::Primary),
+                        button(
+                            button_state,
+                            if editing.is_some() {
+                                "Save Education"
+                            } else {
+                                "Add Education"
+                            },
+                        )
+                        .on_press(Message::AddEducation)
+                        .style(style::Button::Primary),
                     ),
             )
         } else {
             result.push(
                 Row::new().push(
-                    button(clear_state, "Clear")
-                        .on_press(Message::Clear)
-                        .style(style::Button::Secondary),
+                    button(
+                        clear_state,
+                        if editing.is_some() { "Cancel" } else { "Clear" },
+                    )
+                    .on_press(Message::Clear)
+                    .style(style::Button::Secondary),
                 ),
             )
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn skills(
-        skills: &Vec<(String, Option<Proficiency>)>,
+        skills: &[(String, Option<Proficiency>)],
         text: &str,
         selection: Option<Proficiency>,
         text_state: &'a mut text_input::State,
         button_state: &'a mut button::State,
         clear_state: &'a mut button::State,
+        editing: Option<usize>,
+        controls: &'a mut [EntryControls],
     ) -> Column<'a, Message> {
         let text_input = TextInput::new(
             text_state,
@@ -1196,21 +3277,23 @@ impl<'a> Step {
 
         let result = Self::container("Skills")
             .push(Text::new("Add some skills to your resume"))
-            .push(
+            .push(Self::entries(
                 skills
                     .iter()
-                    .cloned()
-                    .fold(Column::new(), |items, (text, proficiency)| {
-                        items.push(Text::new(format!(
+                    .map(|(text, proficiency)| {
+                        format!(
                             "{}: {}",
                             text,
                             match proficiency {
                                 Some(p) => p.to_string().to_lowercase(),
                                 None => "none".to_string(),
                             }
-                        )))
-                    }),
-            )
+                        )
+                    })
+                    .collect(),
+                editing,
+                controls,
+            ))
             .push(text_input)
             .push(Text::new("What's your proficiency?"))
             .push(proficiency_input);
@@ -1219,23 +3302,36 @@ impl<'a> Step {
             result.push(
                 Row::new()
                     .push(
-                        button(clear_state, "Clear")
-                            .on_press(Message::Clear)
-                            .style(style::Button::Secondary),
+                        button(
+                            clear_state,
+                            if editing.is_some() { "Cancel" } else { "Clear" },
+                        )
+                        .on_press(Message::Clear)
+                        .style(style::Button::Secondary),
                     )
                     .push(Space::with_width(Length::Fill))
                     .push(
-                        button(button_state, "Add Skill")
-                            .on_press(Message::AddSkill)
-                            .style(style::Button::Primary),
+                        button(
+                            button_state,
+                            if editing.is_some() {
+                                "Save Skill"
+                            } else {
+                                "Add Skill"
+                            },
+                        )
+                        .on_press(Message::AddSkill)
+                        .style(style::Button::Primary),
                     ),
             )
         } else {
             result.push(
                 Row::new().push(
-                    button(clear_state, "Clear")
-                        .on_press(Message::Clear)
-                        .style(style::Button::Secondary),
+                    button(
+                        clear_state,
+                        if editing.is_some() { "Cancel" } else { "Clear" },
+                    )
+                    .on_press(Message::Clear)
+                    .style(style::Button::Secondary),
                 ),
             )
         }
@@ -1257,6 +3353,60 @@ impl<'a> Step {
             .push(text_input)
     }
 
+    fn contact_info(
+        info: &ContactInfo,
+        email_state: &'a mut text_input::State,
+        phone_state: &'a mut text_input::State,
+        website_state: &'a mut text_input::State,
+        github_state: &'a mut text_input::State,
+        linkedin_state: &'a mut text_input::State,
+    ) -> Column<'a, Message> {
+        // Each input replaces one field of the contact details, and clearing
+        // an input removes that detail from the resume
+        fn input<'a>(
+            state: &'a mut text_input::State,
+            placeholder: &str,
+            info: &ContactInfo,
+            field: fn(&mut ContactInfo) -> &mut Option<String>,
+        ) -> TextInput<'a, Message> {
+            let mut info = info.clone();
+            let value = field(&mut info).clone().unwrap_or_default();
+            TextInput::new(state, placeholder, &value, move |text| {
+                let mut info = info.clone();
+                *field(&mut info) = if text.is_empty() { None } else { Some(text) };
+                Message::ContactsChanged(info)
+            })
+            .padding(10)
+            .width(Length::Fill)
+            .size(30)
+        }
+
+        let result = Self::container("Contact Information")
+            .push(Text::new(
+                "How can employers reach you? Leave anything you don't want to share empty.",
+            ))
+            .push(Text::new("Email address"))
+            .push(input(email_state, "you@example.com", info, |i| {
+                &mut i.email
+            }))
+            .push(Text::new("Phone number"))
+            .push(input(phone_state, "+1 555 123 4567", info, |i| {
+                &mut i.phone
+            }))
+            .push(Text::new("Website"))
+            .push(input(website_state, "example.com", info, |i| {
+                &mut i.website
+            }))
+            .push(Text::new("GitHub username"))
+            .push(input(github_state, "username", info, |i| &mut i.github))
+            .push(Text::new("LinkedIn profile"))
+            .push(input(linkedin_state, "username", info, |i| &mut i.linkedin));
+
+        info.problems()
+            .into_iter()
+            .fold(result, |result, problem| result.push(Text::new(problem)))
+    }
+
     fn description(text: &str, state: &'a mut text_input::State) -> Column<'a, Message> {
         let text_input = TextInput::new(
             state,
@@ -1303,8 +3453,23 @@ impl<'a> Step {
             .push(last_name_text_input)
     }
 
-    fn welcome() -> Column<'a, Message> {
-        Self::container("Welcome")
+    fn welcome(
+        import_file: &str,
+        import_file_state: &'a mut text_input::State,
+        import_button_state: &'a mut button::State,
+        status: &Option<String>,
+    ) -> Column<'a, Message> {
+        let text_input = TextInput::new(
+            import_file_state,
+            "resume.json or europass.xml",
+            import_file,
+            Message::ImportFileChanged,
+        )
+        .padding(10)
+        .width(Length::Fill)
+        .size(30);
+
+        let result = Self::container("Welcome")
             .push(Text::new(
                 "This is a tool to help you automatically create a nice resume webpage.",
             ))
@@ -1312,7 +3477,177 @@ impl<'a> Step {
             .push(Text::new(
                 "Simply go through each page and enter your information!",
             ))
+            .push(Text::new(
+                "Already have a resume.json from JSON Resume, or a Europass CV saved as XML? Import it to fill in every page.",
+            ))
+            .push(text_input)
+            .push(
+                button(import_button_state, "Import JSON Resume or Europass")
+                    .on_press(Message::ImportFile)
+                    .style(style::Button::Secondary),
+            );
+
+        match status {
+            Some(status) => result.push(Text::new(status)),
+            None => result,
+        }
+    }
+}
+
+// Read the dates typed in for a range, which must not end before it starts
+fn parse_range(start: &str, end: &str) -> Result<(Date, Date), String> {
+    let (start, end) = (start.parse::<Date>()?, end.parse::<Date>()?);
+    let backwards = match (start, end) {
+        (Date::Month(..), Date::Month(..)) => end < start,
+        _ => end.year() < start.year(),
+    };
+    if backwards {
+        Err(format!("{} is before {}", end, start))
+    } else {
+        Ok((start, end))
+    }
+}
+
+// Like `parse_range`, but either date may be left out
+fn parse_optional_range(start: &str, end: &str) -> Result<(Option<Date>, Option<Date>), String> {
+    match (start.trim().is_empty(), end.trim().is_empty()) {
+        (true, true) => Ok((None, None)),
+        (false, true) => Ok((Some(start.parse()?), None)),
+        (true, false) => Ok((None, Some(end.parse()?))),
+        (false, false) => parse_range(start, end).map(|(start, end)| (Some(start), Some(end))),
+    }
+}
+
+// Read the form of the Certifications step
+fn read_certification(fields: &[Field]) -> Result<Certification, String> {
+    let [name, issuer, credential_id, issued, expires, url] = texts(fields);
+    if name.trim().is_empty() || issuer.trim().is_empty() {
+        return Err(String::from("A certification needs a name and an issuer"));
+    }
+    let (issued, expires) = parse_optional_range(issued, expires)?;
+    Ok(Certification {
+        name: name.trim().to_string(),
+        issuer: issuer.trim().to_string(),
+        credential_id: non_empty(credential_id),
+        issued,
+        expires,
+        url: non_empty(url),
+    })
+}
+
+// Read the form of the Awards step
+fn read_award(fields: &[Field]) -> Result<Award, String> {
+    let [title, issuer, date, summary] = texts(fields);
+    if title.trim().is_empty() {
+        return Err(String::from("An award needs a title"));
     }
+    Ok(Award {
+        title: title.trim().to_string(),
+        issuer: non_empty(issuer),
+        date: non_empty(date).map(|date| date.parse()).transpose()?,
+        summary: non_empty(summary),
+    })
+}
+
+// Read the form of the Publications step
+fn read_publication(fields: &[Field]) -> Result<Publication, String> {
+    let [title, authors, venue, year, doi, url] = texts(fields);
+    if title.trim().is_empty() {
+        return Err(String::from("A publication needs a title"));
+    }
+    Ok(Publication {
+        title: title.trim().to_string(),
+        authors: authors
+            .split(',')
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+            .map(String::from)
+            .collect(),
+        venue: non_empty(venue),
+        year: non_empty(year)
+            .map(|year| {
+                year.parse()
+                    .map_err(|_| format!("`{}` is not a year", year))
+            })
+            .transpose()?,
+        doi: non_empty(doi),
+        url: non_empty(url),
+    })
+}
+
+// Read the inputs for an entry of the Custom Sections step
+fn read_custom_entry(fields: &[Field], bullets: &[String]) -> Result<CustomEntry, String> {
+    let [heading, subheading, start, end, location] = texts(fields);
+    if heading.trim().is_empty() {
+        return Err(String::from("An entry needs a heading"));
+    }
+    let (start, end) = parse_optional_range(start, end)?;
+    Ok(CustomEntry {
+        heading: heading.trim().to_string(),
+        subheading: non_empty(subheading),
+        start,
+        end,
+        location: non_empty(location),
+        bullets: bullets.to_vec(),
+    })
+}
+
+// The text of each field, for destructuring into as many names
+fn texts<const N: usize>(fields: &[Field]) -> [&str; N] {
+    let mut texts = [""; N];
+    for (text, field) in texts.iter_mut().zip(fields) {
+        *text = &field.text;
+    }
+    texts
+}
+
+fn fill_fields<const N: usize>(fields: &mut [Field], texts: [String; N]) {
+    for (field, text) in fields.iter_mut().zip(texts) {
+        field.text = text;
+    }
+}
+
+// The highlights of an entry, including one still being typed in
+fn with_pending(highlights: &[String], highlight: &str) -> Vec<String> {
+    let mut highlights = highlights.to_vec();
+    highlights.extend(non_empty(highlight));
+    highlights
+}
+
+// Explain what is wrong with a range once both dates have been typed in
+fn date_problem(start: &str, end: &str) -> Option<String> {
+    if start.is_empty() || end.is_empty() {
+        return None;
+    }
+    parse_range(start, end).err()
+}
+
+// Add a new entry to a list, or put back the entry that was being edited
+fn place_entry<T>(list: &mut Vec<T>, editing: &mut Option<usize>, entry: T) {
+    match editing.take() {
+        Some(index) => list[index] = entry,
+        None => list.push(entry),
+    }
+}
+
+// The entry being edited keeps its inputs when it is moved or deleted,
+// so a deleted entry can still be added again
+fn delete_entry<T>(list: &mut Vec<T>, editing: &mut Option<usize>, index: usize) {
+    list.remove(index);
+    *editing = match *editing {
+        Some(i) if i == index => None,
+        Some(i) if i > index => Some(i - 1),
+        other => other,
+    };
+}
+
+fn swap_entries<T>(list: &mut [T], editing: &mut Option<usize>, a: usize, b: usize) {
+    list.swap(a, b);
+    *editing = editing.map(|i| match i {
+        i if i == a => b,
+        i if i == b => a,
+        i => i,
+    });
 }
 
 fn example_image<'a>() -> Container<'a, Message> {
@@ -1334,7 +3669,14 @@ fn example_image<'a>() -> Container<'a, Message> {
 }
 
 fn main() {
+    register_custom_themes();
+    cli::run(std::env::args().skip(1).collect());
+
     let mut settings = Settings::default();
-    settings.window.size = (580, 840);
-    App::run(settings).unwrap();
+    settings.window.size = (WIZARD_WIDTH as u32 * 2 + 100, 840);
+    if let Err(e) = App::run(settings) {
+        eprintln!("error: could not open the resume generator window: {}", e);
+        eprintln!("hint: run `resume-gen help` to use it from the command line");
+        std::process::exit(1);
+    }
 }
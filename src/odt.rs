@@ -0,0 +1,537 @@
+use super::{
+    html::escape, pdf::strip_tags, zip::ZipWriter, Color, HorizontalAlignment, Theme, HTML,
+};
+
+// Lengths are in hundredths of a millimetre. The page is A4 with margins of
+// 15mm, like the PDF.
+const PAGE_WIDTH: u32 = 21000;
+const PAGE_HEIGHT: u32 = 29700;
+const PAGE_MARGIN: u32 = 1500;
+const TEXT_WIDTH: u32 = PAGE_WIDTH - 2 * PAGE_MARGIN;
+// The padding inside the frame of a section
+const SECTION_PADDING: u32 = 200;
+// How far each level of a list is indented
+const LIST_INDENT: u32 = 635;
+
+// Percent bars are drawn as a run of shaded spaces this many characters long
+const BAR_LENGTH: u32 = 20;
+
+const MIMETYPE: &str = "application/vnd.oasis.opendocument.text";
+
+const NAMESPACES: &str = "xmlns:office=\"urn:oasis:names:tc:opendocument:xmlns:office:1.0\" xmlns:style=\"urn:oasis:names:tc:opendocument:xmlns:style:1.0\" xmlns:text=\"urn:oasis:names:tc:opendocument:xmlns:text:1.0\" xmlns:table=\"urn:oasis:names:tc:opendocument:xmlns:table:1.0\" xmlns:fo=\"urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\" xmlns:meta=\"urn:oasis:names:tc:opendocument:xmlns:meta:1.0\" office:version=\"1.2\"";
+
+impl HTML {
+    /// Render this tree as an OpenDocument text file using the theme's
+    /// colors. Grids and sections become tables, except inside lists, which
+    /// cannot hold tables and get their columns one after another instead.
+    pub fn compile_odt(&self, theme: &dyn Theme) -> Vec<u8> {
+        let mut writer = OdtWriter {
+            theme,
+            text_styles: Vec::new(),
+            paragraph_styles: Vec::new(),
+            tables: Vec::new(),
+            list_depth: 0,
+        };
+        let body = writer.block(self, RunStyle::default(), None, TEXT_WIDTH);
+
+        // The mimetype must come first and uncompressed, so that the type
+        // of the file can be read from a fixed place in it
+        let mut zip = ZipWriter::default();
+        zip.store("mimetype", MIMETYPE.as_bytes());
+        zip.deflate("META-INF/manifest.xml", MANIFEST.as_bytes());
+        zip.deflate(
+            "meta.xml",
+            format!(
+                "{}<office:document-meta {}><office:meta><dc:title>{}</dc:title></office:meta></office:document-meta>",
+                XML_DECLARATION,
+                NAMESPACES,
+                escape(self.first_title().unwrap_or_default())
+            )
+            .as_bytes(),
+        );
+        zip.deflate("styles.xml", styles(theme).as_bytes());
+        zip.deflate(
+            "content.xml",
+            format!(
+                "{}<office:document-content {}><office:automatic-styles>{}</office:automatic-styles><office:body><office:text>{}</office:text></office:body></office:document-content>",
+                XML_DECLARATION,
+                NAMESPACES,
+                writer.automatic_styles(),
+                body
+            )
+            .as_bytes(),
+        );
+        zip.finish()
+    }
+}
+
+// How the text of a span looks, passed down from the elements around it
+#[derive(Clone, Copy, Default, PartialEq)]
+struct RunStyle {
+    bold: bool,
+    italic: bool,
+    color: Option<Color>,
+    shading: Option<Color>,
+}
+
+struct OdtWriter<'a> {
+    theme: &'a dyn Theme,
+    // Every distinct look of a span, named T1, T2, and so on
+    text_styles: Vec<RunStyle>,
+    // Every aligned paragraph style, named P1, P2, and so on
+    paragraph_styles: Vec<(&'static str, &'static str)>,
+    // The column widths of every table
+    tables: Vec<Vec<u32>>,
+    // How many list items the current element is inside of
+    list_depth: u32,
+}
+
+impl OdtWriter<'_> {
+    fn block(
+        &mut self,
+        html: &HTML,
+        style: RunStyle,
+        align: Option<&'static str>,
+        width: u32,
+    ) -> String {
+        match html {
+            HTML::Document(items) | HTML::Container(items) | HTML::Column(items) => items
+                .iter()
+                .map(|item| self.block(item, style, align, width))
+                .collect(),
+
+            HTML::Row(items) if items.len() == 1 || self.list_depth > 0 => items
+                .iter()
+                .map(|item| self.block(item, style, align, width))
+                .collect(),
+            HTML::Row(items) => {
+                let columns = items.iter().map(|item| (1, item)).collect();
+                self.table(columns, style, align, width, false)
+            }
+            HTML::Grid(columns) if self.list_depth > 0 => columns
+                .iter()
+                .map(|(_, item)| self.block(item, style, align, width))
+                .collect(),
+            HTML::Grid(columns) => {
+                let columns = columns
+                    .iter()
+                    .map(|(span, item)| (u32::from(*span), item))
+                    .collect();
+                self.table(columns, style, align, width, false)
+            }
+            HTML::Section(contents) if self.list_depth > 0 => {
+                self.block(contents, style, align, width)
+            }
+            HTML::Section(contents) => self.table(vec![(1, contents)], style, align, width, true),
+
+            HTML::Aligned(contents, hori, _) => self.block(
+                contents,
+                style,
+                match hori {
+                    HorizontalAlignment::Left => Some("left"),
+                    HorizontalAlignment::Right => Some("right"),
+                    HorizontalAlignment::Center => Some("center"),
+                    HorizontalAlignment::SameAsParent => align,
+                },
+                width,
+            ),
+
+            HTML::Title(_) => {
+                let spans = self.spans(html, style);
+                format!(
+                    "<text:p text:style-name=\"{}\">{}</text:p>",
+                    self.paragraph_style("Title", align),
+                    spans
+                )
+            }
+            HTML::SectionTitle(_) => {
+                let spans = self.spans(html, style);
+                format!(
+                    "<text:h text:style-name=\"{}\" text:outline-level=\"1\">{}</text:h>",
+                    self.paragraph_style("Heading_20_1", align),
+                    spans
+                )
+            }
+
+            HTML::Text(_)
+            | HTML::Span(_)
+            | HTML::Paragraph(_)
+            | HTML::Raw(_)
+            | HTML::Badge(_)
+            | HTML::Link(_, _)
+            | HTML::PercentBar(_, _) => {
+                let spans = self.spans(html, style);
+                format!(
+                    "<text:p text:style-name=\"{}\">{}</text:p>",
+                    self.paragraph_style("Standard", align),
+                    spans
+                )
+            }
+
+            HTML::OrderedList(items) | HTML::UnorderedList(items) => {
+                let list_style = match html {
+                    HTML::OrderedList(_) => "Numbers",
+                    _ => "Bullets",
+                };
+                self.list_depth += 1;
+                let mut xml = format!("<text:list text:style-name=\"{}\">", list_style);
+                for item in items {
+                    let mut contents = self.block(item, style, None, width);
+                    // Every item starts with a paragraph to hold its label
+                    if !contents.starts_with("<text:p") && !contents.starts_with("<text:h") {
+                        contents.insert_str(0, "<text:p/>");
+                    }
+                    xml.push_str(&format!("<text:list-item>{}</text:list-item>", contents));
+                }
+                self.list_depth -= 1;
+                xml + "</text:list>"
+            }
+
+            HTML::Italics(contents) => self.block(
+                contents,
+                RunStyle {
+                    italic: true,
+                    ..style
+                },
+                align,
+                width,
+            ),
+            HTML::Bold(contents) => self.block(
+                contents,
+                RunStyle {
+                    bold: true,
+                    ..style
+                },
+                align,
+                width,
+            ),
+            HTML::ColoredForeground(contents, color) => self.block(
+                contents,
+                RunStyle {
+                    color: Some(*color),
+                    ..style
+                },
+                align,
+                width,
+            ),
+            HTML::ColoredBackground(contents, color) => self.block(
+                contents,
+                RunStyle {
+                    shading: Some(*color),
+                    ..style
+                },
+                align,
+                width,
+            ),
+            HTML::Rectangle(contents, _, _) | HTML::Microformat(contents, _) => {
+                self.block(contents, style, align, width)
+            }
+
+            HTML::JsonLd(_) | HTML::FadeIn(_) => String::new(),
+        }
+    }
+
+    fn spans(&mut self, html: &HTML, style: RunStyle) -> String {
+        match html {
+            HTML::Text(text) | HTML::Span(text) | HTML::Title(text) | HTML::SectionTitle(text) => {
+                self.span(text, style)
+            }
+            HTML::Raw(markup) => self.span(&strip_tags(markup), style),
+            // Colored like the badges of the webpage
+            HTML::Badge(label) => self.span(
+                &format!("\u{a0}{}\u{a0}", label),
+                RunStyle {
+                    bold: true,
+                    color: Some(Color::DefaultBackground),
+                    shading: Some(Color::DefaultSubtitle),
+                    ..style
+                },
+            ),
+            HTML::PercentBar(part, label) => {
+                let filled = (part.min(&100) * BAR_LENGTH + 50) / 100;
+                let bar = |length: u32| "\u{a0}".repeat(length as usize);
+                self.span(
+                    &bar(filled),
+                    RunStyle {
+                        shading: Some(Color::DefaultSectionTitle),
+                        ..style
+                    },
+                ) + &self.span(
+                    &bar(BAR_LENGTH - filled),
+                    RunStyle {
+                        shading: Some(Color::Grey),
+                        ..style
+                    },
+                ) + &self.span(&format!(" {}", label), style)
+            }
+            HTML::Link(contents, url) => format!(
+                "<text:a xlink:type=\"simple\" xlink:href=\"{}\" text:style-name=\"Internet_20_link\">{}</text:a>",
+                escape(url),
+                self.spans(contents, style)
+            ),
+            HTML::Italics(contents) => self.spans(
+                contents,
+                RunStyle {
+                    italic: true,
+                    ..style
+                },
+            ),
+            HTML::Bold(contents) => self.spans(
+                contents,
+                RunStyle {
+                    bold: true,
+                    ..style
+                },
+            ),
+            HTML::ColoredForeground(contents, color) => self.spans(
+                contents,
+                RunStyle {
+                    color: Some(*color),
+                    ..style
+                },
+            ),
+            HTML::ColoredBackground(contents, color) => self.spans(
+                contents,
+                RunStyle {
+                    shading: Some(*color),
+                    ..style
+                },
+            ),
+            other => other
+                .children()
+                .into_iter()
+                .map(|child| self.spans(child, style))
+                .collect(),
+        }
+    }
+
+    fn span(&mut self, text: &str, style: RunStyle) -> String {
+        if text.is_empty() {
+            return String::new();
+        }
+        if style == RunStyle::default() {
+            return whitespace(&escape(text));
+        }
+        let index = match self.text_styles.iter().position(|s| *s == style) {
+            Some(index) => index,
+            None => {
+                self.text_styles.push(style);
+                self.text_styles.len() - 1
+            }
+        };
+        format!(
+            "<text:span text:style-name=\"T{}\">{}</text:span>",
+            index + 1,
+            whitespace(&escape(text))
+        )
+    }
+
+    // The name of a paragraph style, with an automatic style built on it
+    // when the paragraph is aligned
+    fn paragraph_style(&mut self, parent: &'static str, align: Option<&'static str>) -> String {
+        let align = match align {
+            Some(align) => align,
+            None => return String::from(parent),
+        };
+        let index = match self
+            .paragraph_styles
+            .iter()
+            .position(|style| *style == (parent, align))
+        {
+            Some(index) => index,
+            None => {
+                self.paragraph_styles.push((parent, align));
+                self.paragraph_styles.len() - 1
+            }
+        };
+        format!("P{}", index + 1)
+    }
+
+    // A table of one row, splitting `width` between the cells in proportion
+    // to their spans. Only sections have borders.
+    fn table(
+        &mut self,
+        cells: Vec<(u32, &HTML)>,
+        style: RunStyle,
+        align: Option<&'static str>,
+        width: u32,
+        section: bool,
+    ) -> String {
+        let total: u32 = cells.iter().map(|(span, _)| span).sum::<u32>().max(1);
+        let widths: Vec<u32> = cells.iter().map(|(span, _)| width * span / total).collect();
+        let padding = if section { SECTION_PADDING } else { 0 };
+
+        self.tables.push(widths.clone());
+        let name = format!("Table{}", self.tables.len());
+        let cell_style = if section { "SectionCell" } else { "Cell" };
+
+        let mut xml = format!(
+            "<table:table table:name=\"{}\" table:style-name=\"{}\">",
+            name, name
+        );
+        for i in 0..widths.len() {
+            xml.push_str(&format!(
+                "<table:table-column table:style-name=\"{}.{}\"/>",
+                name,
+                i + 1
+            ));
+        }
+        xml.push_str("<table:table-row>");
+        for ((_, item), width) in cells.iter().zip(widths) {
+            let mut contents = self.block(item, style, align, width.saturating_sub(2 * padding));
+            // Every cell must hold at least one paragraph
+            if contents.is_empty() {
+                contents.push_str("<text:p/>");
+            }
+            xml.push_str(&format!(
+                "<table:table-cell table:style-name=\"{}\" office:value-type=\"string\">{}</table:table-cell>",
+                cell_style, contents
+            ));
+        }
+        xml + "</table:table-row></table:table>"
+    }
+
+    fn automatic_styles(&self) -> String {
+        let mut xml = String::new();
+        for (i, style) in self.text_styles.iter().enumerate() {
+            let mut properties = String::new();
+            if style.bold {
+                properties.push_str(" fo:font-weight=\"bold\"");
+            }
+            if style.italic {
+                properties.push_str(" fo:font-style=\"italic\"");
+            }
+            if let Some(color) = style.color {
+                properties.push_str(&format!(
+                    " fo:color=\"{}\"",
+                    self.theme.get_color_hex(color)
+                ));
+            }
+            if let Some(shading) = style.shading {
+                properties.push_str(&format!(
+                    " fo:background-color=\"{}\"",
+                    self.theme.get_color_hex(shading)
+                ));
+            }
+            xml.push_str(&format!(
+                "<style:style style:name=\"T{}\" style:family=\"text\"><style:text-properties{}/></style:style>",
+                i + 1,
+                properties
+            ));
+        }
+
+        for (i, (parent, align)) in self.paragraph_styles.iter().enumerate() {
+            xml.push_str(&format!(
+                "<style:style style:name=\"P{}\" style:family=\"paragraph\" style:parent-style-name=\"{}\"><style:paragraph-properties fo:text-align=\"{}\"/></style:style>",
+                i + 1,
+                parent,
+                align
+            ));
+        }
+
+        for (i, widths) in self.tables.iter().enumerate() {
+            xml.push_str(&format!(
+                "<style:style style:name=\"Table{}\" style:family=\"table\"><style:table-properties style:width=\"{}\" table:align=\"left\"/></style:style>",
+                i + 1,
+                length(widths.iter().sum())
+            ));
+            for (j, width) in widths.iter().enumerate() {
+                xml.push_str(&format!(
+                    "<style:style style:name=\"Table{}.{}\" style:family=\"table-column\"><style:table-column-properties style:column-width=\"{}\"/></style:style>",
+                    i + 1,
+                    j + 1,
+                    length(*width)
+                ));
+            }
+        }
+
+        xml.push_str(&format!(
+            "<style:style style:name=\"Cell\" style:family=\"table-cell\"><style:table-cell-properties fo:padding=\"0mm\" fo:border=\"none\"/></style:style><style:style style:name=\"SectionCell\" style:family=\"table-cell\"><style:table-cell-properties fo:padding=\"{}\" fo:border=\"0.5pt solid {}\"/></style:style>",
+            length(SECTION_PADDING),
+            self.theme.get_color_hex(Color::Grey)
+        ));
+        xml
+    }
+}
+
+// Runs of spaces, tabs, and line breaks collapse into one space in
+// OpenDocument unless they are spelled out
+fn whitespace(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut spaces = 0;
+    for ch in text.chars().chain(core::iter::once('\0')) {
+        if ch == ' ' {
+            spaces += 1;
+            continue;
+        }
+        match spaces {
+            0 => {}
+            1 => result.push(' '),
+            n => result.push_str(&format!(" <text:s text:c=\"{}\"/>", n - 1)),
+        }
+        spaces = 0;
+        match ch {
+            '\0' => {}
+            '\t' => result.push_str("<text:tab/>"),
+            '\n' => result.push_str("<text:line-break/>"),
+            ch => result.push(ch),
+        }
+    }
+    result
+}
+
+fn length(hundredths: u32) -> String {
+    format!("{}.{:02}mm", hundredths / 100, hundredths % 100)
+}
+
+fn styles(theme: &dyn Theme) -> String {
+    let mut lists = String::new();
+    for &(name, numbered) in [("Bullets", false), ("Numbers", true)].iter() {
+        lists.push_str(&format!("<text:list-style style:name=\"{}\">", name));
+        for level in 1..=10 {
+            let (element, label) = if numbered {
+                (
+                    "text:list-level-style-number",
+                    "style:num-suffix=\".\" style:num-format=\"1\"",
+                )
+            } else {
+                (
+                    "text:list-level-style-bullet",
+                    "text:bullet-char=\"\u{2022}\"",
+                )
+            };
+            lists.push_str(&format!(
+                "<{element} text:level=\"{}\" {}><style:list-level-properties text:list-level-position-and-space-mode=\"label-alignment\"><style:list-level-label-alignment text:label-followed-by=\"listtab\" text:list-tab-stop-position=\"{indent}\" fo:text-indent=\"-{}\" fo:margin-left=\"{indent}\"/></style:list-level-properties></{element}>",
+                level,
+                label,
+                length(LIST_INDENT),
+                element = element,
+                indent = length(LIST_INDENT * level),
+            ));
+        }
+        lists.push_str("</text:list-style>");
+    }
+
+    format!(
+        "{}<office:document-styles {}><office:styles>\
+<style:default-style style:family=\"paragraph\"><style:paragraph-properties fo:margin-top=\"0mm\" fo:margin-bottom=\"1.4mm\"/><style:text-properties fo:font-size=\"10pt\" fo:color=\"{}\"/></style:default-style>\
+<style:style style:name=\"Standard\" style:family=\"paragraph\" style:class=\"text\"/>\
+<style:style style:name=\"Title\" style:family=\"paragraph\" style:parent-style-name=\"Standard\" style:next-style-name=\"Standard\" style:class=\"chapter\"><style:paragraph-properties fo:margin-bottom=\"2mm\"/><style:text-properties fo:font-size=\"24pt\" fo:font-weight=\"bold\"/></style:style>\
+<style:style style:name=\"Heading_20_1\" style:display-name=\"Heading 1\" style:family=\"paragraph\" style:parent-style-name=\"Standard\" style:next-style-name=\"Standard\" style:default-outline-level=\"1\" style:class=\"text\"><style:paragraph-properties fo:margin-top=\"2mm\" fo:margin-bottom=\"2mm\" fo:keep-with-next=\"always\"/><style:text-properties fo:font-size=\"14pt\"/></style:style>\
+<style:style style:name=\"Internet_20_link\" style:display-name=\"Internet link\" style:family=\"text\"><style:text-properties style:text-underline-style=\"solid\" style:text-underline-width=\"auto\" style:text-underline-color=\"font-color\"/></style:style>\
+{}</office:styles><office:automatic-styles><style:page-layout style:name=\"Page\"><style:page-layout-properties fo:page-width=\"{}\" fo:page-height=\"{}\" fo:margin-top=\"{m}\" fo:margin-bottom=\"{m}\" fo:margin-left=\"{m}\" fo:margin-right=\"{m}\" fo:background-color=\"{}\"/></style:page-layout></office:automatic-styles>\
+<office:master-styles><style:master-page style:name=\"Standard\" style:page-layout-name=\"Page\"/></office:master-styles></office:document-styles>",
+        XML_DECLARATION,
+        NAMESPACES,
+        theme.get_color_hex(Color::DefaultForeground),
+        lists,
+        length(PAGE_WIDTH),
+        length(PAGE_HEIGHT),
+        theme.get_color_hex(Color::DefaultBackground),
+        m = length(PAGE_MARGIN),
+    )
+}
+
+const XML_DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n";
+
+const MANIFEST: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<manifest:manifest xmlns:manifest=\"urn:oasis:names:tc:opendocument:xmlns:manifest:1.0\" manifest:version=\"1.2\"><manifest:file-entry manifest:full-path=\"/\" manifest:version=\"1.2\" manifest:media-type=\"application/vnd.oasis.opendocument.text\"/><manifest:file-entry manifest:full-path=\"content.xml\" manifest:media-type=\"text/xml\"/><manifest:file-entry manifest:full-path=\"styles.xml\" manifest:media-type=\"text/xml\"/><manifest:file-entry manifest:full-path=\"meta.xml\" manifest:media-type=\"text/xml\"/></manifest:manifest>";
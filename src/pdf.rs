@@ -0,0 +1,439 @@
+use super::{Color, HorizontalAlignment, Theme, HTML};
+use core::fmt::{Display, Formatter};
+use genpdf::{
+    elements::{
+        Break, LinearLayout, OrderedList, PaddedElement, Paragraph, TableLayout, UnorderedList,
+    },
+    fonts::{FontData, FontFamily},
+    render::Area,
+    style::{Style, StyledString},
+    Alignment, Context, Element, Margins, Mm, PaperSize, Position, RenderResult, Size,
+};
+
+// The fonts are embedded in every PDF, so the output looks the same
+// everywhere and its text can be selected and searched.
+const REGULAR: &[u8] = include_bytes!("../assets/fonts/DejaVuSans.ttf");
+const BOLD: &[u8] = include_bytes!("../assets/fonts/DejaVuSans-Bold.ttf");
+const ITALIC: &[u8] = include_bytes!("../assets/fonts/DejaVuSans-Oblique.ttf");
+const BOLD_ITALIC: &[u8] = include_bytes!("../assets/fonts/DejaVuSans-BoldOblique.ttf");
+
+const FONT_SIZE: u8 = 10;
+const TITLE_FONT_SIZE: u8 = 24;
+const SECTION_TITLE_FONT_SIZE: u8 = 14;
+const PAGE_MARGIN: f32 = 15.0;
+
+#[derive(Debug)]
+pub struct PdfError(String);
+
+impl Display for PdfError {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "could not create PDF: {}", self.0)
+    }
+}
+
+impl std::error::Error for PdfError {}
+
+impl From<genpdf::error::Error> for PdfError {
+    fn from(e: genpdf::error::Error) -> Self {
+        Self(e.to_string())
+    }
+}
+
+impl From<lopdf::Error> for PdfError {
+    fn from(e: lopdf::Error) -> Self {
+        Self(format!("{:?}", e))
+    }
+}
+
+impl HTML {
+    /// Render this tree as a paginated A4 PDF using the theme's colors
+    pub fn compile_pdf(&self, theme: &dyn Theme) -> Result<Vec<u8>, PdfError> {
+        let font = |data: &[u8]| FontData::new(data.to_vec(), None);
+        let mut doc = genpdf::Document::new(FontFamily {
+            regular: font(REGULAR)?,
+            bold: font(BOLD)?,
+            italic: font(ITALIC)?,
+            bold_italic: font(BOLD_ITALIC)?,
+        });
+        doc.set_paper_size(PaperSize::A4);
+        doc.set_font_size(FONT_SIZE);
+        if let Some(title) = self.first_title() {
+            doc.set_title(title);
+        }
+
+        let mut decorator = genpdf::SimplePageDecorator::new();
+        decorator.set_margins(PAGE_MARGIN);
+        doc.set_page_decorator(decorator);
+
+        let style = Style::new().with_color(rgb(theme, Color::DefaultForeground));
+        doc.push(self.pdf_block(theme, style, Alignment::Left).styled(style));
+
+        let mut bytes = Vec::new();
+        doc.render(&mut bytes)?;
+        finish(&bytes, theme.get_color_rgb(Color::DefaultBackground))
+    }
+
+    // The text of the first title in the tree, used as the PDF's title
+    pub(crate) fn first_title(&self) -> Option<&str> {
+        match self {
+            Self::Title(title) => Some(title),
+            other => other.children().into_iter().find_map(Self::first_title),
+        }
+    }
+
+    fn pdf_block(&self, theme: &dyn Theme, style: Style, align: Alignment) -> Block {
+        match self {
+            Self::Document(items) | Self::Container(items) | Self::Column(items) => {
+                let mut layout = LinearLayout::vertical();
+                for item in items {
+                    layout.push(item.pdf_block(theme, style, align));
+                }
+                Block::new(layout)
+            }
+
+            Self::Row(items) if items.len() == 1 => items[0].pdf_block(theme, style, align),
+            Self::Row(items) => {
+                let mut table = TableLayout::new(vec![1; items.len()]);
+                let mut row = table.row();
+                for item in items {
+                    row.push_element(item.pdf_block(theme, style, align).padded(Margins::trbl(
+                        0, 1, 0, 1,
+                    )));
+                }
+                // The number of cells always matches the number of columns
+                row.push().unwrap();
+                Block::new(table)
+            }
+            Self::Grid(columns) => {
+                let mut table =
+                    TableLayout::new(columns.iter().map(|(width, _)| *width as usize).collect());
+                let mut row = table.row();
+                for (_, item) in columns {
+                    row.push_element(
+                        item.pdf_block(theme, style, align)
+                            .padded(Margins::trbl(0, 1, 0, 1)),
+                    );
+                }
+                row.push().unwrap();
+                Block::new(table)
+            }
+
+            Self::Aligned(contents, hori, _) => contents.pdf_block(
+                theme,
+                style,
+                match hori {
+                    HorizontalAlignment::Left => Alignment::Left,
+                    HorizontalAlignment::Right => Alignment::Right,
+                    HorizontalAlignment::Center => Alignment::Center,
+                    HorizontalAlignment::SameAsParent => align,
+                },
+            ),
+
+            Self::Title(title) => Block::new(
+                Paragraph::new(StyledString::new(
+                    title.clone(),
+                    style.bold().with_font_size(TITLE_FONT_SIZE),
+                ))
+                .aligned(align)
+                .padded(Margins::trbl(0, 0, 2, 0)),
+            ),
+            Self::SectionTitle(title) => Block::new(
+                Paragraph::new(StyledString::new(
+                    title.clone(),
+                    style.with_font_size(SECTION_TITLE_FONT_SIZE),
+                ))
+                .aligned(align)
+                .padded(Margins::trbl(2, 0, 2, 0)),
+            ),
+
+            Self::Text(_)
+            | Self::Span(_)
+            | Self::Paragraph(_)
+            | Self::Raw(_)
+            | Self::Badge(_)
+            | Self::Link(_, _) => {
+                let mut strings = Vec::new();
+                self.pdf_inline(theme, style, &mut strings);
+                let mut paragraph = Paragraph::default().aligned(align);
+                for s in strings {
+                    paragraph.push(s);
+                }
+                Block::new(paragraph.padded(Margins::trbl(0, 0, 2, 0)))
+            }
+
+            Self::OrderedList(items) => {
+                let mut list = OrderedList::new();
+                for item in items {
+                    list.push(item.pdf_block(theme, style, Alignment::Left));
+                }
+                Block::new(list)
+            }
+            Self::UnorderedList(items) => {
+                let mut list = UnorderedList::new();
+                for item in items {
+                    list.push(item.pdf_block(theme, style, Alignment::Left));
+                }
+                Block::new(list)
+            }
+
+            Self::PercentBar(part, label) => Block::new(PercentBar {
+                part: *part,
+                label: label.clone(),
+                fill: rgb(theme, Color::DefaultSectionTitle),
+                track: rgb(theme, Color::Grey),
+                style,
+            }),
+
+            Self::Section(contents) => Block::new(
+                Frame {
+                    inner: contents.pdf_block(theme, style, align).padded(4),
+                    color: rgb(theme, Color::Grey),
+                    is_first: true,
+                }
+                .padded(Margins::trbl(0, 0, 4, 0)),
+            ),
+
+            Self::Italics(contents) => contents.pdf_block(theme, style.italic(), align),
+            Self::Bold(contents) => contents.pdf_block(theme, style.bold(), align),
+            Self::ColoredForeground(contents, color) => {
+                contents.pdf_block(theme, style.with_color(rgb(theme, *color)), align)
+            }
+
+            // Backgrounds other than the page's are not drawn
+            Self::Rectangle(contents, _, _) | Self::ColoredBackground(contents, _) => {
+                contents.pdf_block(theme, style, align)
+            }
+            Self::Microformat(contents, _) => contents.pdf_block(theme, style, align),
+
+            Self::JsonLd(_) | Self::FadeIn(_) => Block::new(Break::new(0)),
+        }
+    }
+
+    fn pdf_inline(&self, theme: &dyn Theme, style: Style, strings: &mut Vec<StyledString>) {
+        match self {
+            Self::Text(text) | Self::Span(text) | Self::Title(text) | Self::SectionTitle(text) => {
+                strings.push(StyledString::new(text.clone(), style))
+            }
+            Self::Raw(markup) => strings.push(StyledString::new(strip_tags(markup), style)),
+            // Badges have no background in PDFs, so they are set apart by
+            // brackets and color instead
+            Self::Badge(label) => strings.push(StyledString::new(
+                format!("[{}]", label),
+                style.bold().with_color(rgb(theme, Color::DefaultSubtitle)),
+            )),
+            Self::PercentBar(part, label) => {
+                strings.push(StyledString::new(format!("{} ({}%)", label, part), style))
+            }
+            Self::Italics(contents) => contents.pdf_inline(theme, style.italic(), strings),
+            Self::Bold(contents) => contents.pdf_inline(theme, style.bold(), strings),
+            Self::ColoredForeground(contents, color) => {
+                contents.pdf_inline(theme, style.with_color(rgb(theme, *color)), strings)
+            }
+            other => {
+                for child in other.children() {
+                    child.pdf_inline(theme, style, strings)
+                }
+            }
+        }
+    }
+}
+
+fn rgb(theme: &dyn Theme, color: Color) -> genpdf::style::Color {
+    let (r, g, b) = theme.get_color_rgb(color);
+    genpdf::style::Color::Rgb(r, g, b)
+}
+
+pub(crate) fn strip_tags(markup: &str) -> String {
+    let mut in_tag = false;
+    markup
+        .chars()
+        .filter(|ch| match ch {
+            '<' => {
+                in_tag = true;
+                false
+            }
+            '>' => {
+                in_tag = false;
+                false
+            }
+            _ => !in_tag,
+        })
+        .collect()
+}
+
+// genpdf can only draw lines of a fixed width, so filled shapes are
+// made from horizontal lines spaced closely enough to overlap.
+const LINE_SPACING: f32 = 0.3;
+
+fn fill(area: &Area<'_>, x: Mm, width: Mm, height: Mm, color: genpdf::style::Color) {
+    let mut y = Mm::from(LINE_SPACING / 2.0);
+    while y < height {
+        area.draw_line(
+            vec![Position::new(x, y), Position::new(x + width, y)],
+            Style::new().with_color(color),
+        );
+        y += Mm::from(LINE_SPACING);
+    }
+}
+
+// A type-erased element, so that `pdf_block` can return any kind of element
+struct Block(Box<dyn Element>);
+
+impl Block {
+    fn new(element: impl Element + 'static) -> Self {
+        Self(Box::new(element))
+    }
+
+    fn padded(self, padding: impl Into<Margins>) -> PaddedElement<Self> {
+        PaddedElement::new(self, padding)
+    }
+}
+
+impl Element for Block {
+    fn render(
+        &mut self,
+        context: &Context,
+        area: Area<'_>,
+        style: Style,
+    ) -> Result<RenderResult, genpdf::error::Error> {
+        self.0.render(context, area, style)
+    }
+}
+
+// A progress bar with its label printed beside it
+struct PercentBar {
+    part: u32,
+    label: String,
+    fill: genpdf::style::Color,
+    track: genpdf::style::Color,
+    style: Style,
+}
+
+impl Element for PercentBar {
+    fn render(
+        &mut self,
+        context: &Context,
+        area: Area<'_>,
+        style: Style,
+    ) -> Result<RenderResult, genpdf::error::Error> {
+        let style = style.and(self.style);
+        let height = style.line_height(&context.font_cache);
+        if area.size().height < height {
+            return Ok(RenderResult {
+                size: Size::new(0, 0),
+                has_more: true,
+            });
+        }
+
+        let width = area.size().width * 0.65;
+        let bar = height * 0.6;
+        let mut bar_area = area.clone();
+        bar_area.add_offset(Position::new(0, (height - bar) * 0.5));
+        fill(&bar_area, Mm::from(0), width, bar, self.track);
+        fill(
+            &bar_area,
+            Mm::from(0),
+            width * (f64::from(self.part.min(100)) / 100.0),
+            bar,
+            self.fill,
+        );
+
+        let mut label_area = area.clone();
+        label_area.add_offset(Position::new(width + Mm::from(2), 0));
+        label_area.print_str(&context.font_cache, Position::default(), style, &self.label)?;
+
+        Ok(RenderResult {
+            size: Size::new(area.size().width, height),
+            has_more: false,
+        })
+    }
+}
+
+// Like genpdf's `FramedElement`, but with a frame color that does not
+// change the default text color of the content
+struct Frame<E: Element> {
+    inner: E,
+    color: genpdf::style::Color,
+    is_first: bool,
+}
+
+impl<E: Element> Element for Frame<E> {
+    fn render(
+        &mut self,
+        context: &Context,
+        area: Area<'_>,
+        style: Style,
+    ) -> Result<RenderResult, genpdf::error::Error> {
+        let result = self.inner.render(context, area.clone(), style)?;
+        let (width, height) = (area.size().width, result.size.height);
+        let line = Style::new().with_color(self.color);
+        area.draw_line(vec![Position::new(0, 0), Position::new(0, height)], line);
+        area.draw_line(
+            vec![Position::new(width, 0), Position::new(width, height)],
+            line,
+        );
+        if self.is_first {
+            area.draw_line(vec![Position::new(0, 0), Position::new(width, 0)], line);
+        }
+        if !result.has_more {
+            area.draw_line(
+                vec![Position::new(0, height), Position::new(width, height)],
+                line,
+            );
+        }
+        self.is_first = false;
+        Ok(result)
+    }
+}
+
+// genpdf cannot fill shapes behind the content of a page, so the theme's
+// background is added afterwards as a rectangle drawn before each page.
+// The streams are also compressed here, since printpdf leaves them
+// uncompressed in debug builds and the embedded fonts are large.
+fn finish(bytes: &[u8], (r, g, b): (u8, u8, u8)) -> Result<Vec<u8>, PdfError> {
+    let mut doc = lopdf::Document::load_mem(bytes)?;
+    let pages = if (r, g, b) == (255, 255, 255) {
+        vec![]
+    } else {
+        doc.get_pages().into_values().collect()
+    };
+
+    for page_id in pages {
+        let page = doc.get_object(page_id)?.as_dict()?;
+        let size = match page.get(b"MediaBox").and_then(|o| o.as_array()) {
+            Ok(media_box) if media_box.len() == 4 => media_box
+                .iter()
+                .map(|n| n.as_f64().or_else(|_| n.as_i64().map(|n| n as f64)))
+                .collect::<Result<Vec<f64>, _>>()?,
+            _ => vec![0.0, 0.0, 595.28, 841.89],
+        };
+
+        let content = format!(
+            "q {:.3} {:.3} {:.3} rg {} {} {} {} re f Q\n",
+            f64::from(r) / 255.0,
+            f64::from(g) / 255.0,
+            f64::from(b) / 255.0,
+            size[0],
+            size[1],
+            size[2] - size[0],
+            size[3] - size[1]
+        );
+        let background =
+            doc.add_object(lopdf::Stream::new(lopdf::Dictionary::new(), content.into_bytes()));
+
+        let page = doc.get_object_mut(page_id)?.as_dict_mut()?;
+        let mut contents = match page.get(b"Contents") {
+            Ok(lopdf::Object::Array(contents)) => contents.clone(),
+            Ok(contents) => vec![contents.clone()],
+            Err(_) => vec![],
+        };
+        contents.insert(0, lopdf::Object::Reference(background));
+        page.set("Contents", contents);
+    }
+
+    doc.compress();
+    let mut result = Vec::new();
+    doc.save_to(&mut result)
+        .map_err(|e| PdfError(e.to_string()))?;
+    Ok(result)
+}
@@ -0,0 +1,211 @@
+use super::Message;
+use iced::{
+    container, progress_bar, Align, Background, Column, Container, Element, Length, ProgressBar,
+    Row, Text,
+};
+use resume::{Color, HorizontalAlignment, Theme, HTML};
+
+// Text sizes used in place of the stylesheet's
+const TITLE_SIZE: u16 = 40;
+const SECTION_TITLE_SIZE: u16 = 26;
+const TEXT_SIZE: u16 = 16;
+
+// How inherited text is drawn, like CSS's `color` and `text-align`
+#[derive(Clone, Copy)]
+struct Style {
+    color: iced::Color,
+    align: iced::HorizontalAlignment,
+}
+
+/// A native approximation of how `html` looks once compiled with `theme`.
+/// Fonts, bold and italics are not reproduced, but layout and colors are.
+pub fn preview<'a>(html: &HTML, theme: &dyn Theme) -> Element<'a, Message> {
+    let style = Style {
+        color: color(theme, Color::DefaultForeground),
+        align: iced::HorizontalAlignment::Left,
+    };
+    Container::new(element(html, theme, style))
+        .width(Length::Fill)
+        .padding(10)
+        .style(Fill(color(theme, Color::DefaultBackground)))
+        .into()
+}
+
+fn element<'a>(html: &HTML, theme: &dyn Theme, style: Style) -> Element<'a, Message> {
+    match html {
+        HTML::Document(items) | HTML::Container(items) | HTML::Column(items) => items
+            .iter()
+            .fold(Column::new().spacing(10), |column, item| {
+                column.push(element(item, theme, style))
+            })
+            .into(),
+        HTML::Row(items) => items
+            .iter()
+            .fold(Row::new().spacing(10), |row, item| {
+                row.push(Container::new(element(item, theme, style)).width(Length::Fill))
+            })
+            .into(),
+        HTML::Grid(columns) => columns
+            .iter()
+            .fold(Row::new().spacing(10), |row, (width, item)| {
+                row.push(
+                    Container::new(element(item, theme, style))
+                        .width(Length::FillPortion(u16::from(*width))),
+                )
+            })
+            .into(),
+
+        HTML::OrderedList(items) => list(items, theme, style, |i| format!("{}.", i + 1)),
+        HTML::UnorderedList(items) => list(items, theme, style, |_| String::from("•")),
+
+        HTML::Aligned(content, hori, _) => {
+            let align = match hori {
+                HorizontalAlignment::Left => iced::HorizontalAlignment::Left,
+                HorizontalAlignment::Right => iced::HorizontalAlignment::Right,
+                HorizontalAlignment::Center => iced::HorizontalAlignment::Center,
+                HorizontalAlignment::SameAsParent => style.align,
+            };
+            element(content, theme, Style { align, ..style })
+        }
+
+        HTML::Title(title) => text(title, style).size(TITLE_SIZE).into(),
+        HTML::SectionTitle(title) => text(title, style).size(SECTION_TITLE_SIZE).into(),
+        HTML::Text(_) | HTML::Span(_) | HTML::Paragraph(_) | HTML::Link(_, _) | HTML::Badge(_) => {
+            text(&inline(html), style).into()
+        }
+
+        HTML::PercentBar(part, label) => Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(
+                ProgressBar::new(0.0..=100.0, *part as f32)
+                    .height(Length::Units(TEXT_SIZE))
+                    .style(Bar {
+                        fill: color(theme, Color::DefaultSectionTitle),
+                        track: color(theme, Color::Grey),
+                    }),
+            )
+            .push(Text::new(label.clone()).size(TEXT_SIZE).color(style.color))
+            .into(),
+
+        HTML::Section(content) => Container::new(element(content, theme, style))
+            .width(Length::Fill)
+            .padding(15)
+            .style(Border(color(theme, Color::Grey)))
+            .into(),
+        HTML::Rectangle(content, _, background) | HTML::ColoredBackground(content, background) => {
+            Container::new(element(content, theme, style))
+                .width(Length::Fill)
+                .style(Fill(color(theme, *background)))
+                .into()
+        }
+
+        HTML::ColoredForeground(content, foreground) => element(
+            content,
+            theme,
+            Style {
+                color: color(theme, *foreground),
+                ..style
+            },
+        ),
+        HTML::Italics(content) | HTML::Bold(content) | HTML::Microformat(content, _) => {
+            element(content, theme, style)
+        }
+
+        // Neither trusted markup nor animations can be shown natively, and
+        // data for machines is not shown at all
+        HTML::Raw(_) | HTML::JsonLd(_) | HTML::FadeIn(_) => Column::new().into(),
+    }
+}
+
+fn list<'a>(
+    items: &[HTML],
+    theme: &dyn Theme,
+    style: Style,
+    marker: fn(usize) -> String,
+) -> Element<'a, Message> {
+    items
+        .iter()
+        .enumerate()
+        .fold(Column::new().spacing(5), |column, (i, item)| {
+            column.push(
+                Row::new()
+                    .spacing(10)
+                    .push(Text::new(marker(i)).size(TEXT_SIZE).color(style.color))
+                    .push(element(
+                        item,
+                        theme,
+                        Style {
+                            align: iced::HorizontalAlignment::Left,
+                            ..style
+                        },
+                    )),
+            )
+        })
+        .into()
+}
+
+fn text(content: &str, style: Style) -> Text {
+    Text::new(content)
+        .size(TEXT_SIZE)
+        .color(style.color)
+        .horizontal_alignment(style.align)
+        .width(Length::Fill)
+}
+
+// The text of an element and everything inside it, as it reads on the page
+fn inline(html: &HTML) -> String {
+    match html {
+        HTML::Text(text)
+        | HTML::Span(text)
+        | HTML::Title(text)
+        | HTML::SectionTitle(text)
+        | HTML::Badge(text) => text.clone(),
+        other => other.children().into_iter().map(inline).collect(),
+    }
+}
+
+fn color(theme: &dyn Theme, color: Color) -> iced::Color {
+    let (r, g, b) = theme.get_color_rgb(color);
+    iced::Color::from_rgb8(r, g, b)
+}
+
+struct Fill(iced::Color);
+
+impl container::StyleSheet for Fill {
+    fn style(&self) -> container::Style {
+        container::Style {
+            background: Some(Background::Color(self.0)),
+            ..container::Style::default()
+        }
+    }
+}
+
+// Sections are drawn as cards with a rounded border
+struct Border(iced::Color);
+
+impl container::StyleSheet for Border {
+    fn style(&self) -> container::Style {
+        container::Style {
+            border_radius: 4.0,
+            border_width: 1.0,
+            border_color: self.0,
+            ..container::Style::default()
+        }
+    }
+}
+
+struct Bar {
+    fill: iced::Color,
+    track: iced::Color,
+}
+
+impl progress_bar::StyleSheet for Bar {
+    fn style(&self) -> progress_bar::Style {
+        progress_bar::Style {
+            background: Background::Color(self.track),
+            bar: Background::Color(self.fill),
+            border_radius: 4.0,
+        }
+    }
+}
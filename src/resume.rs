@@ -1,18 +1,64 @@
-use super::{Color, HorizontalAlignment, VerticalAlignment, HTML};
+use super::{
+    ClassicTemplate, Color, Date, HorizontalAlignment, Layout, SectionId, Template,
+    VerticalAlignment, HTML,
+};
 use core::fmt::{Display, Error, Formatter};
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Work {
-    pub start_year: u32,
-    pub end_year: u32,
+    // Older documents only stored years, under these names
+    #[serde(alias = "start_year")]
+    pub start: Date,
+    #[serde(alias = "end_year")]
+    pub end: Date,
 
     pub position: String,
     pub company: String,
 
+    // A summary line shown next to the position
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub description: String,
+    // Achievements listed under the position, one bullet each
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<String>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+impl Work {
+    pub fn generate(&self) -> HTML {
+        self.generate_entry(true)
+    }
+
+    // The entry, leaving out its dates when a template shows them elsewhere
+    pub(crate) fn generate_entry(&self, with_dates: bool) -> HTML {
+        let mut title = vec![
+            HTML::bold(HTML::span(&self.position)),
+            HTML::span(format!(" at {}", self.company)),
+        ];
+        if with_dates {
+            title.push(HTML::span(", "));
+            title.push(HTML::italics(HTML::span(Date::range(
+                &self.start,
+                &self.end,
+            ))));
+        }
+        if !self.description.is_empty() {
+            title.push(HTML::span(format!(". {}", self.description)));
+        }
+
+        if self.highlights.is_empty() {
+            HTML::paragraph(title)
+        } else {
+            HTML::col(vec![
+                HTML::paragraph(title),
+                HTML::ul(self.highlights.iter().map(HTML::text).collect()),
+            ])
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Degree {
     Associates,
     Bachelors,
@@ -55,17 +101,351 @@ impl From<Degree> for String {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Education {
-    pub start_year: u32,
-    pub end_year: u32,
+    #[serde(alias = "start_year")]
+    pub start: Date,
+    #[serde(alias = "end_year")]
+    pub end: Date,
 
     pub school: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub field: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub degree: Option<Degree>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+impl Education {
+    pub fn generate(&self) -> HTML {
+        self.generate_entry(true)
+    }
+
+    pub(crate) fn generate_entry(&self, with_dates: bool) -> HTML {
+        let mut items = match &self.field {
+            Some(field) => vec![HTML::span(format!("Studied {} at ", field))],
+            None => vec![HTML::span("Attended ")],
+        };
+        items.push(HTML::bold(HTML::span(&self.school)));
+        if with_dates {
+            items.push(HTML::span(", "));
+            items.push(HTML::italics(HTML::span(Date::range(
+                &self.start,
+                &self.end,
+            ))));
+        }
+        if let Some(degree) = self.degree {
+            items.push(HTML::span(format!(" and acheived {}", degree)));
+        }
+        HTML::paragraph(items)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Project {
+    pub name: String,
+    // Where the project can be seen or used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    // Where its source code lives
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<Date>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<Date>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub technologies: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<String>,
+}
+
+impl Project {
+    /// When the project ran, if that is known
+    pub fn dates(&self) -> Option<String> {
+        Date::optional_range(self.start, self.end)
+    }
+
+    /// The entry for this project in the Projects section of a resume
+    pub fn generate(&self) -> HTML {
+        self.generate_entry(true)
+    }
+
+    pub(crate) fn generate_entry(&self, with_dates: bool) -> HTML {
+        let name = HTML::bold(HTML::span(&self.name));
+        let mut title = vec![match &self.url {
+            Some(url) => HTML::link(name, url),
+            None => name,
+        }];
+        if let Some(role) = &self.role {
+            title.push(HTML::span(format!(", {}", role)));
+        }
+        if let Some(dates) = self.dates().filter(|_| with_dates) {
+            title.push(HTML::span(", "));
+            title.push(HTML::italics(HTML::span(dates)));
+        }
+        if let Some(repository) = &self.repository {
+            title.push(HTML::span(" ("));
+            title.push(HTML::link(HTML::span("source"), repository));
+            title.push(HTML::span(")"));
+        }
+
+        let mut entry = vec![HTML::paragraph(title)];
+        if !self.technologies.is_empty() {
+            let mut tags = Vec::new();
+            for technology in &self.technologies {
+                if !tags.is_empty() {
+                    tags.push(HTML::span(" "));
+                }
+                tags.push(HTML::badge(technology));
+            }
+            entry.push(HTML::paragraph(tags));
+        }
+        if !self.highlights.is_empty() {
+            entry.push(HTML::ul(self.highlights.iter().map(HTML::text).collect()));
+        }
+        HTML::col(entry)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Certification {
+    pub name: String,
+    pub issuer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issued: Option<Date>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<Date>,
+    // Where the certification can be verified
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl Certification {
+    /// Whether the certification has expired as of today
+    pub fn is_expired(&self) -> bool {
+        self.expires.is_some_and(|expires| expires.has_passed())
+    }
+
+    /// When the certification was issued and when it expires, such as
+    /// `Issued Mar 2021, expired Mar 2024`
+    pub fn validity(&self) -> Option<String> {
+        let expires = self.expires.map(|expires| {
+            if self.is_expired() {
+                format!("expired {}", expires)
+            } else {
+                format!("expires {}", expires)
+            }
+        });
+        match (self.issued, expires) {
+            (Some(issued), Some(expires)) => Some(format!("Issued {}, {}", issued, expires)),
+            (Some(issued), None) => Some(format!("Issued {}", issued)),
+            (None, Some(expires)) => {
+                let mut expires = expires;
+                expires[..1].make_ascii_uppercase();
+                Some(expires)
+            }
+            (None, None) => None,
+        }
+    }
+
+    pub fn generate(&self) -> HTML {
+        let name = HTML::bold(HTML::span(&self.name));
+        let mut items = vec![
+            match &self.url {
+                Some(url) => HTML::link(name, url),
+                None => name,
+            },
+            HTML::span(format!(", {}", self.issuer)),
+        ];
+        if let Some(id) = &self.credential_id {
+            items.push(HTML::span(format!(". Credential ID {}", id)));
+        }
+
+        let mut result = vec![HTML::paragraph(items)];
+        if let Some(validity) = self.validity() {
+            let validity = HTML::paragraph(vec![HTML::italics(HTML::span(validity))]);
+            // Expired certifications are flagged so they stand out for renewal
+            result.push(if self.is_expired() {
+                HTML::fg(validity, Color::Red)
+            } else {
+                validity
+            });
+        }
+        HTML::col(result)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Award {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<Date>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+
+impl Award {
+    pub fn generate(&self) -> HTML {
+        let mut items = vec![HTML::bold(HTML::span(&self.title))];
+        if let Some(issuer) = &self.issuer {
+            items.push(HTML::span(format!(", {}", issuer)));
+        }
+        if let Some(date) = self.date {
+            items.push(HTML::span(", "));
+            items.push(HTML::italics(HTML::span(date)));
+        }
+        if let Some(summary) = &self.summary {
+            items.push(HTML::span(format!(". {}", summary)));
+        }
+        HTML::paragraph(items)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Publication {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+    // The journal, conference or publisher
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub venue: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub year: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doi: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl Publication {
+    /// Where the publication can be read, preferring its DOI
+    pub fn link(&self) -> Option<String> {
+        match (&self.doi, &self.url) {
+            (Some(doi), _) => Some(format!(
+                "https://doi.org/{}",
+                doi.trim_start_matches("https://doi.org/")
+            )),
+            (None, Some(url)) => Some(url.clone()),
+            (None, None) => None,
+        }
+    }
+
+    /// The publication as a citation, such as
+    /// `A. Lovelace, C. Babbage. Sketch of the Analytical Engine. Scientific Memoirs, 1843.`
+    pub fn citation(&self) -> String {
+        let mut citation = String::new();
+        if !self.authors.is_empty() {
+            citation += &format!("{}. ", self.authors.join(", "));
+        }
+        citation += &format!("{}.", self.title);
+        match (&self.venue, self.year) {
+            (Some(venue), Some(year)) => citation += &format!(" {}, {}.", venue, year),
+            (Some(venue), None) => citation += &format!(" {}.", venue),
+            (None, Some(year)) => citation += &format!(" {}.", year),
+            (None, None) => {}
+        }
+        citation
+    }
+
+    pub fn generate(&self) -> HTML {
+        let mut items = Vec::new();
+        if !self.authors.is_empty() {
+            items.push(HTML::span(format!("{}. ", self.authors.join(", "))));
+        }
+        let title = HTML::bold(HTML::span(&self.title));
+        items.push(match self.link() {
+            Some(link) => HTML::link(title, link),
+            None => title,
+        });
+        items.push(HTML::span("."));
+        if let Some(venue) = &self.venue {
+            items.push(HTML::span(" "));
+            items.push(HTML::italics(HTML::span(venue)));
+        }
+        match (&self.venue, self.year) {
+            (Some(_), Some(year)) => items.push(HTML::span(format!(", {}.", year))),
+            (Some(_), None) => items.push(HTML::span(".")),
+            (None, Some(year)) => items.push(HTML::span(format!(" {}.", year))),
+            (None, None) => {}
+        }
+        HTML::paragraph(items)
+    }
+}
+
+/// A section the resume model has no fields for, such as volunteering or
+/// speaking engagements
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomSection {
+    pub title: String,
+    #[serde(default)]
+    pub entries: Vec<CustomEntry>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomEntry {
+    pub heading: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subheading: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<Date>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<Date>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bullets: Vec<String>,
+}
+
+impl CustomEntry {
+    pub fn dates(&self) -> Option<String> {
+        Date::optional_range(self.start, self.end)
+    }
+
+    /// The entry styled like the entries of the Professional Experience section
+    pub fn generate(&self) -> HTML {
+        self.generate_entry(true)
+    }
+
+    pub(crate) fn generate_entry(&self, with_dates: bool) -> HTML {
+        let mut title = vec![HTML::bold(HTML::span(&self.heading))];
+        if let Some(subheading) = &self.subheading {
+            title.push(HTML::span(format!(", {}", subheading)));
+        }
+        if let Some(location) = &self.location {
+            title.push(HTML::span(format!(", {}", location)));
+        }
+        if let Some(dates) = self.dates().filter(|_| with_dates) {
+            title.push(HTML::span(", "));
+            title.push(HTML::italics(HTML::span(dates)));
+        }
+
+        if self.bullets.is_empty() {
+            HTML::paragraph(title)
+        } else {
+            HTML::col(vec![
+                HTML::paragraph(title),
+                HTML::ul(self.bullets.iter().map(HTML::text).collect()),
+            ])
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Proficiency {
     None,
     Barely,
@@ -120,91 +500,344 @@ impl From<Proficiency> for u32 {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ContactInfo {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub phone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub website: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub github: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub linkedin: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+impl ContactInfo {
+    /// Every contact detail that is filled in, as a label, the text to show,
+    /// and the URL it links to
+    pub fn links(&self) -> Vec<(&'static str, String, String)> {
+        let mut links = Vec::new();
+        if let Some(email) = &self.email {
+            links.push(("Email", email.clone(), format!("mailto:{}", email.trim())));
+        }
+        if let Some(phone) = &self.phone {
+            let number: String = phone
+                .chars()
+                .filter(|c| c.is_ascii_digit() || *c == '+')
+                .collect();
+            links.push(("Phone", phone.clone(), format!("tel:{}", number)));
+        }
+        // Addresses are shown without their scheme, since a bare username
+        // means little on paper
+        let mut address = |label, url: String| {
+            let text = url
+                .split_once("://")
+                .map(|(_, rest)| rest)
+                .unwrap_or(&url)
+                .trim_end_matches('/')
+                .to_string();
+            links.push((label, text, url));
+        };
+        if let Some(website) = &self.website {
+            address("Website", web_url(website));
+        }
+        if let Some(github) = &self.github {
+            address(
+                "GitHub",
+                profile_url(github, "github.com", "https://github.com/"),
+            );
+        }
+        if let Some(linkedin) = &self.linkedin {
+            address(
+                "LinkedIn",
+                profile_url(linkedin, "linkedin.com", "https://www.linkedin.com/in/"),
+            );
+        }
+        links
+    }
+
+    /// Describe everything that is wrong with the filled in contact details.
+    /// Profiles may be given either as a username or as a full URL.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if let Some(email) = &self.email {
+            let valid = match email.trim().split_once('@') {
+                Some((user, domain)) => {
+                    !user.is_empty()
+                        && !domain.contains('@')
+                        && domain.contains('.')
+                        && !domain.starts_with('.')
+                        && !domain.ends_with('.')
+                        && !email.trim().contains(char::is_whitespace)
+                }
+                None => false,
+            };
+            if !valid {
+                problems.push(format!("`{}` is not an email address", email));
+            }
+        }
+        if let Some(phone) = &self.phone {
+            let digits = phone.chars().filter(char::is_ascii_digit).count();
+            let valid =
+                phone.trim().chars().enumerate().all(|(i, c)| {
+                    c.is_ascii_digit() || " -().".contains(c) || (c == '+' && i == 0)
+                });
+            if !valid || !(7..=15).contains(&digits) {
+                problems.push(format!("`{}` is not a phone number", phone));
+            }
+        }
+        if let Some(website) = &self.website {
+            let url = web_url(website);
+            let host = url.split_once("://").map(|(_, host)| host).unwrap_or_default();
+            if !(url.starts_with("http://") || url.starts_with("https://"))
+                || !host.contains('.')
+                || website.trim().contains(char::is_whitespace)
+            {
+                problems.push(format!("`{}` is not a website address", website));
+            }
+        }
+        if let Some(github) = &self.github {
+            // GitHub usernames are alphanumeric with single inner hyphens
+            let name = profile_name(github, "github.com");
+            if name.is_empty()
+                || name.len() > 39
+                || name.starts_with('-')
+                || name.ends_with('-')
+                || name.contains("--")
+                || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            {
+                problems.push(format!("`{}` is not a GitHub username", github));
+            }
+        }
+        if let Some(linkedin) = &self.linkedin {
+            let name = profile_name(linkedin, "linkedin.com/in");
+            if name.len() < 3
+                || name.len() > 100
+                || !name.chars().all(|c| c.is_alphanumeric() || c == '-')
+            {
+                problems.push(format!("`{}` is not a LinkedIn profile", linkedin));
+            }
+        }
+        problems
+    }
+}
+
+// Add a scheme to addresses like `example.com`
+fn web_url(address: &str) -> String {
+    let address = address.trim();
+    if address.contains("://") {
+        address.to_string()
+    } else {
+        format!("https://{}", address)
+    }
+}
+
+// The username in a profile given either as a bare username or as a URL
+/// `text` without surrounding whitespace, or `None` if nothing is left. Used
+/// wherever an empty input or field means a missing value.
+pub fn non_empty(text: &str) -> Option<String> {
+    Some(text.trim().to_string()).filter(|text| !text.is_empty())
+}
+
+pub(crate) fn profile_name<'a>(profile: &'a str, site: &str) -> &'a str {
+    let profile = profile.trim().trim_start_matches('@');
+    match profile.find(site) {
+        Some(i) => profile[i + site.len()..].trim_matches('/'),
+        None => profile,
+    }
+}
+
+pub(crate) fn profile_url(profile: &str, site: &str, base: &str) -> String {
+    if profile.contains(site) {
+        web_url(profile)
+    } else {
+        format!("{}{}", base, profile.trim().trim_start_matches('@'))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Resume {
     pub first_name: String,
     pub last_name: String,
 
+    #[serde(default)]
     pub description: String,
+    #[serde(default)]
     pub contact_info: ContactInfo,
 
+    #[serde(default)]
     pub profession: String,
+    #[serde(default, with = "crate::document::skills")]
     pub skills: Vec<(String, Option<Proficiency>)>,
+    #[serde(default)]
     pub education: Vec<Education>,
+    #[serde(default)]
     pub work_experience: Vec<Work>,
+    #[serde(default)]
+    pub projects: Vec<Project>,
+    #[serde(default)]
+    pub certifications: Vec<Certification>,
+    #[serde(default)]
+    pub awards: Vec<Award>,
+    #[serde(default)]
+    pub publications: Vec<Publication>,
+    #[serde(default)]
+    pub custom_sections: Vec<CustomSection>,
+    #[serde(default)]
+    pub layout: Layout,
 }
 
 impl Resume {
+    /// Lay this resume out with the classic template
     pub fn generate(&self) -> HTML {
-        HTML::html(vec![
-            HTML::container(vec![
-                HTML::col(vec![
-                    HTML::row(vec![
-                        HTML::section(HTML::col(vec![
-                            HTML::aligned(
-                                HTML::fg(HTML::title(format!("{} {}", self.first_name, self.last_name)), Color::DefaultTitle),
-                                HorizontalAlignment::Center,
-                                VerticalAlignment::SameAsParent
-                            ),
-                            HTML::aligned(
-                                HTML::fg(HTML::italics(HTML::section_title(&self.profession)), Color::DefaultSubtitle),
-                                HorizontalAlignment::Center,
-                                VerticalAlignment::SameAsParent
-                            ),
-                            // HTML::fg(HTML::text(&self.description), Color::Green),
-                            HTML::text(&self.description),
-                        ])),
-                        HTML::section(HTML::col(vec![
-                            HTML::aligned(
-                                HTML::fg(HTML::italics(HTML::section_title("Skills")), Color::DefaultSubtitle),
-                                HorizontalAlignment::Center,
-                                VerticalAlignment::SameAsParent
-                            ),
-                            HTML::aligned(HTML::ul(self.skills.iter().map(|(skill, level)| {
-                                if let Some(level) = level {
-                                    HTML::row(vec![HTML::text(skill), HTML::PercentBar(u32::from(*level) * 100 / u32::from(Proficiency::Expert), level.to_string())])
-                                } else {
-                                    HTML::text(skill)
-                                }
-                            }).collect::<Vec<HTML>>()), HorizontalAlignment::Center,
-                            VerticalAlignment::SameAsParent)
-                        ])),
-                        // HTML::section(HTML::aligned(HTML::text("hello world!"), HorizontalAlignment::Center, VerticalAlignment::Center)),
-                    ]),
-                    HTML::section(HTML::col(vec![
-                        HTML::aligned(HTML::fg(HTML::section_title("Education"), Color::DefaultSectionTitle), HorizontalAlignment::Center, VerticalAlignment::SameAsParent),
-                        HTML::ol(self.education.iter().map(|e| {
-                            match (&e.field, &e.degree) {
-                                (Some(field), None) => {
-                                    HTML::text(format!("Studied {} at <b>{}</b> from <i>{}</i> to <i>{}</i>", field, e.school, e.start_year, e.end_year))
-                                }
-                                (Some(field), Some(degree)) => {
-                                    HTML::text(format!("Studied {} at <b>{}</b> from <i>{}</i> to <i>{}</i> and acheived {}", field, e.school, e.start_year, e.end_year, degree.to_string()))
-                                }
-                                (None, None) => {
-                                    HTML::text(format!("Attended <b>{}</b> from <i>{}</i> to <i>{}</i>", e.school, e.start_year, e.end_year))
-                                }
-                                (None, Some(degree)) => {
-                                    HTML::text(format!("Attended <b>{}</b> from <i>{}</i> to <i>{}</i> and acheived {}", e.school, e.start_year, e.end_year, degree.to_string()))
-                                }
+        self.generate_with(&ClassicTemplate)
+    }
+
+    pub fn generate_with(&self, template: &dyn Template) -> HTML {
+        template.generate(self)
+    }
+
+    // The name, profession, contact links and description, marked with the
+    // properties of an h-card. Templates mark what holds them as the h-card.
+    pub(crate) fn header(&self) -> Vec<HTML> {
+        let mut profession = HTML::section_title(&self.profession);
+        if !self.profession.is_empty() {
+            profession = HTML::microformat(profession, "p-job-title");
+        }
+        let mut header = vec![
+            HTML::aligned(
+                HTML::fg(
+                    HTML::microformat(
+                        HTML::title(format!("{} {}", self.first_name, self.last_name)),
+                        "p-name",
+                    ),
+                    Color::DefaultTitle,
+                ),
+                HorizontalAlignment::Center,
+                VerticalAlignment::SameAsParent,
+            ),
+            HTML::aligned(
+                HTML::fg(HTML::italics(profession), Color::DefaultSubtitle),
+                HorizontalAlignment::Center,
+                VerticalAlignment::SameAsParent,
+            ),
+        ];
+        let links = self.contact_info.links();
+        if !links.is_empty() {
+            header.push(HTML::aligned(
+                HTML::paragraph(
+                    links
+                        .into_iter()
+                        .enumerate()
+                        .flat_map(|(i, (label, text, url))| {
+                            let property = match label {
+                                "Email" => "u-email",
+                                "Phone" => "p-tel",
+                                _ => "u-url",
+                            };
+                            let link =
+                                HTML::microformat(HTML::link(HTML::span(text), url), property);
+                            if i == 0 {
+                                vec![link]
+                            } else {
+                                vec![HTML::span(" | "), link]
                             }
-                        }).collect::<Vec<HTML>>()),
-                        HTML::aligned(HTML::fg(HTML::section_title("Professional Experience"), Color::DefaultSectionTitle), HorizontalAlignment::Center, VerticalAlignment::SameAsParent),
-                        HTML::ul(self.work_experience.iter().map(|j| {
-                            HTML::text(format!("<b>{}</b> at {} from <i>{}</i> to <i>{}</i>. {}", j.position, j.company, j.start_year, j.end_year, j.description))
-                        }).collect::<Vec<HTML>>())
-                    ])),
-                ])
-            ])
-        ])
+                        })
+                        .collect(),
+                ),
+                HorizontalAlignment::Center,
+                VerticalAlignment::SameAsParent,
+            ));
+        }
+        // header.push(HTML::fg(HTML::text(&self.description), Color::Green));
+        if !self.description.is_empty() {
+            header.push(HTML::microformat(HTML::text(&self.description), "p-note"));
+        }
+        header
+    }
+
+    // The entries of a section, one for each item it lists
+    pub(crate) fn section_entries(&self, id: &SectionId) -> Vec<HTML> {
+        match id {
+            SectionId::Skills => self
+                .skills
+                .iter()
+                .map(|(skill, level)| {
+                    if let Some(level) = level {
+                        HTML::row(vec![
+                            HTML::text(skill),
+                            HTML::PercentBar(
+                                u32::from(*level) * 100 / u32::from(Proficiency::Expert),
+                                level.to_string(),
+                            ),
+                        ])
+                    } else {
+                        HTML::text(skill)
+                    }
+                })
+                .collect(),
+            SectionId::Education => self.education.iter().map(Education::generate).collect(),
+            SectionId::Experience => self.work_experience.iter().map(Work::generate).collect(),
+            SectionId::Projects => self.projects.iter().map(Project::generate).collect(),
+            SectionId::Certifications => self
+                .certifications
+                .iter()
+                .map(Certification::generate)
+                .collect(),
+            SectionId::Awards => self.awards.iter().map(Award::generate).collect(),
+            SectionId::Publications => self
+                .publications
+                .iter()
+                .map(Publication::generate)
+                .collect(),
+            SectionId::Custom(_) => self.custom_entries(id).map(CustomEntry::generate).collect(),
+        }
     }
+
+    // The title and entries of a section, which is left out when it has no entries
+    pub(crate) fn section(&self, id: &SectionId) -> Option<HTML> {
+        let entries = self.section_entries(id);
+        if entries.is_empty() {
+            return None;
+        }
+
+        Some(HTML::col(match id {
+            // Skills are set apart from the sections that list a history
+            SectionId::Skills => vec![
+                HTML::aligned(
+                    HTML::fg(
+                        HTML::italics(HTML::section_title(id.title())),
+                        Color::DefaultSubtitle,
+                    ),
+                    HorizontalAlignment::Center,
+                    VerticalAlignment::SameAsParent,
+                ),
+                HTML::aligned(
+                    HTML::ul(entries),
+                    HorizontalAlignment::Center,
+                    VerticalAlignment::SameAsParent,
+                ),
+            ],
+            _ => vec![
+                section_heading(id),
+                match id {
+                    SectionId::Education => HTML::ol(entries),
+                    _ => HTML::ul(entries),
+                },
+            ],
+        }))
+    }
+}
+
+// The centered title every section but Skills is shown under
+pub(crate) fn section_heading(id: &SectionId) -> HTML {
+    HTML::aligned(
+        HTML::fg(HTML::section_title(id.title()), Color::DefaultSectionTitle),
+        HorizontalAlignment::Center,
+        VerticalAlignment::SameAsParent,
+    )
 }
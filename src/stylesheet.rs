@@ -0,0 +1,82 @@
+use super::HTML;
+
+// A small subset of Bootstrap 4.3.1 that covers the classes emitted by
+// `HTML::compile`, so that offline documents look the same as online ones.
+// Each group is only included when an element that needs it is present.
+
+const BASE: &str = r#"
+*, *::before, *::after { box-sizing: border-box; }
+html { line-height: 1.15; -webkit-text-size-adjust: 100%; }
+body { margin: 0; font-size: 1rem; font-weight: 400; line-height: 1.5; text-align: left; }
+h1, h4 { margin-top: 0; margin-bottom: .5rem; font-weight: 500; line-height: 1.2; }
+h1 { font-size: 2.5rem; }
+h4 { font-size: 1.5rem; }
+p, ol, ul { margin-top: 0; margin-bottom: 1rem; }
+ol ol, ul ul, ol ul, ul ol { margin-bottom: 0; }
+b { font-weight: bolder; }
+a { color: #007bff; text-decoration: none; background-color: transparent; }
+a:hover { color: #0056b3; text-decoration: underline; }
+"#;
+
+const CONTAINER: &str = r#"
+.container { width: 100%; padding-right: 15px; padding-left: 15px; margin-right: auto; margin-left: auto; }
+@media (min-width: 576px) { .container { max-width: 540px; } }
+@media (min-width: 768px) { .container { max-width: 720px; } }
+@media (min-width: 992px) { .container { max-width: 960px; } }
+@media (min-width: 1200px) { .container { max-width: 1140px; } }
+"#;
+
+const GRID: &str = r#"
+.row { display: flex; flex-wrap: wrap; margin-right: -15px; margin-left: -15px; }
+.col { position: relative; width: 100%; padding-right: 15px; padding-left: 15px; flex-basis: 0; flex-grow: 1; max-width: 100%; }
+.no-gutters { margin-right: 0; margin-left: 0; }
+.no-gutters > .col { padding-right: 0; padding-left: 0; }
+"#;
+
+// The column widths the templates give `HTML::Grid`
+const GRID_COLUMNS: &str = r#"
+.col-3, .col-4, .col-8, .col-9 { position: relative; width: 100%; padding-right: 15px; padding-left: 15px; }
+.col-3 { flex: 0 0 25%; max-width: 25%; }
+.col-4 { flex: 0 0 33.333333%; max-width: 33.333333%; }
+.col-8 { flex: 0 0 66.666667%; max-width: 66.666667%; }
+.col-9 { flex: 0 0 75%; max-width: 75%; }
+.no-gutters > [class*="col-"] { padding-right: 0; padding-left: 0; }
+"#;
+
+const CARD: &str = r#"
+.card { position: relative; display: flex; flex-direction: column; min-width: 0; word-wrap: break-word; background-clip: border-box; border: 1px solid rgba(0, 0, 0, .125); border-radius: .25rem; }
+.card-body { flex: 1 1 auto; padding: 1.25rem; }
+"#;
+
+const PROGRESS: &str = r#"
+.progress { display: flex; height: 1rem; overflow: hidden; font-size: .75rem; background-color: #e9ecef; border-radius: .25rem; }
+.progress-bar { display: flex; flex-direction: column; justify-content: center; color: #fff; text-align: center; white-space: nowrap; transition: width .6s ease; }
+"#;
+
+const BADGE: &str = r#"
+.badge { display: inline-block; padding: .25em .4em; font-size: 75%; font-weight: 700; line-height: 1; text-align: center; white-space: nowrap; vertical-align: baseline; border-radius: .25rem; }
+"#;
+
+/// The CSS needed to render `html` without Bootstrap
+pub(crate) fn stylesheet(html: &HTML) -> String {
+    let mut css = String::from(BASE);
+    if html.contains(&|h| matches!(h, HTML::Container(_))) {
+        css += CONTAINER;
+    }
+    if html.contains(&|h| matches!(h, HTML::Row(_) | HTML::Column(_) | HTML::Grid(_))) {
+        css += GRID;
+    }
+    if html.contains(&|h| matches!(h, HTML::Grid(_))) {
+        css += GRID_COLUMNS;
+    }
+    if html.contains(&|h| matches!(h, HTML::Section(_))) {
+        css += CARD;
+    }
+    if html.contains(&|h| matches!(h, HTML::PercentBar(_, _))) {
+        css += PROGRESS;
+    }
+    if html.contains(&|h| matches!(h, HTML::Badge(_))) {
+        css += BADGE;
+    }
+    css
+}
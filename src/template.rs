@@ -0,0 +1,172 @@
+use super::{
+    resume::section_heading, Color, Date, HorizontalAlignment, Resume, SectionId,
+    VerticalAlignment, HTML,
+};
+use core::iter;
+
+/// Decides the structure of a resume: which sections go where and how their
+/// entries are arranged. Colors and section cards are left to the `Theme`,
+/// so any template can be used with any theme.
+pub trait Template {
+    fn generate(&self, resume: &Resume) -> HTML;
+}
+
+/// A single column of sections, with skills beside the name when they come first
+pub struct ClassicTemplate;
+impl Template for ClassicTemplate {
+    fn generate(&self, resume: &Resume) -> HTML {
+        let sections = resume.visible_sections();
+        let mut top = vec![HTML::section(card(resume.header()))];
+        let mut rest = sections.as_slice();
+        // Skills sit beside the name when they come first
+        if let Some((SectionId::Skills, others)) = sections.split_first() {
+            top.extend(resume.section(&SectionId::Skills).map(HTML::section));
+            rest = others;
+        }
+
+        page(
+            iter::once(HTML::row(top))
+                .chain(
+                    rest.iter()
+                        .filter_map(|id| resume.section(id))
+                        .map(HTML::section),
+                )
+                .collect(),
+        )
+    }
+}
+
+/// A narrow sidebar with the name, skills, certifications and awards beside
+/// a wide column with everything else
+pub struct SidebarTemplate;
+impl Template for SidebarTemplate {
+    fn generate(&self, resume: &Resume) -> HTML {
+        let mut side = vec![HTML::section(card(resume.header()))];
+        let mut main = Vec::new();
+        for id in resume.visible_sections() {
+            if let Some(section) = resume.section(&id) {
+                match id {
+                    SectionId::Skills | SectionId::Certifications | SectionId::Awards => {
+                        side.push(HTML::section(section))
+                    }
+                    _ => main.push(HTML::section(section)),
+                }
+            }
+        }
+
+        page(vec![HTML::grid(vec![
+            (4, HTML::col(side)),
+            (8, HTML::col(main)),
+        ])])
+    }
+}
+
+/// Everything in one card, with skills listed on a single line, to fit as
+/// much as possible on one page
+pub struct CompactTemplate;
+impl Template for CompactTemplate {
+    fn generate(&self, resume: &Resume) -> HTML {
+        let mut items = resume.header();
+        for id in resume.visible_sections() {
+            match id {
+                SectionId::Skills if !resume.skills.is_empty() => {
+                    let skills: Vec<String> = resume
+                        .skills
+                        .iter()
+                        .map(|(skill, level)| match level {
+                            Some(level) => format!("{} ({})", skill, level),
+                            None => skill.clone(),
+                        })
+                        .collect();
+                    items.push(HTML::paragraph(vec![
+                        HTML::bold(HTML::span("Skills: ")),
+                        HTML::span(skills.join(", ")),
+                    ]));
+                }
+                SectionId::Skills => {}
+                _ => items.extend(resume.section(&id)),
+            }
+        }
+
+        page(vec![HTML::section(card(items))])
+    }
+}
+
+/// Dated entries are shown on a timeline, with their dates in a column on
+/// the left
+pub struct TimelineTemplate;
+impl Template for TimelineTemplate {
+    fn generate(&self, resume: &Resume) -> HTML {
+        let mut sections = vec![HTML::section(card(resume.header()))];
+        for id in resume.visible_sections() {
+            let dated: Vec<(String, HTML)> = match &id {
+                SectionId::Education => resume
+                    .education
+                    .iter()
+                    .map(|e| (Date::range(&e.start, &e.end), e.generate_entry(false)))
+                    .collect(),
+                SectionId::Experience => resume
+                    .work_experience
+                    .iter()
+                    .map(|j| (Date::range(&j.start, &j.end), j.generate_entry(false)))
+                    .collect(),
+                SectionId::Projects => resume
+                    .projects
+                    .iter()
+                    .map(|p| (p.dates().unwrap_or_default(), p.generate_entry(false)))
+                    .collect(),
+                SectionId::Custom(_) => resume
+                    .custom_entries(&id)
+                    .map(|entry| {
+                        (
+                            entry.dates().unwrap_or_default(),
+                            entry.generate_entry(false),
+                        )
+                    })
+                    .collect(),
+                // Sections without date ranges are shown as usual
+                _ => {
+                    sections.extend(resume.section(&id).map(HTML::section));
+                    continue;
+                }
+            };
+            if dated.is_empty() {
+                continue;
+            }
+
+            sections.push(HTML::section(HTML::col(
+                iter::once(section_heading(&id))
+                    .chain(dated.into_iter().map(|(dates, entry)| {
+                        HTML::grid(vec![
+                            (
+                                3,
+                                HTML::aligned(
+                                    HTML::fg(
+                                        HTML::italics(HTML::text(dates)),
+                                        Color::DefaultSubtitle,
+                                    ),
+                                    HorizontalAlignment::Left,
+                                    VerticalAlignment::SameAsParent,
+                                ),
+                            ),
+                            (9, entry),
+                        ])
+                    }))
+                    .collect(),
+            )));
+        }
+
+        page(sections)
+    }
+}
+
+// The column holding the header, marked as an h-card so that machines can
+// tell whose resume it is and how to reach them
+fn card(items: Vec<HTML>) -> HTML {
+    HTML::microformat(HTML::col(items), "h-card")
+}
+
+// Every template fills the same fixed width page
+fn page(items: Vec<HTML>) -> HTML {
+    HTML::html(vec![HTML::container(vec![HTML::col(items)])])
+}
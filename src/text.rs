@@ -0,0 +1,396 @@
+use super::{Award, Certification, CustomEntry, Date, Education, Project, Resume, SectionId, Work};
+
+// Both renderers emit one linear document in reading order, with contact
+// information at the top, since applicant tracking systems cannot follow
+// multi-column layouts.
+
+impl Resume {
+    /// Render this resume as a single-column Markdown document
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!(
+            "# {} {}\n\n",
+            escape(&self.first_name),
+            escape(&self.last_name)
+        );
+        if !self.profession.is_empty() {
+            md += &format!("**{}**\n\n", escape(&self.profession));
+        }
+
+        let contacts = self.contact_lines();
+        if !contacts.is_empty() {
+            md += &contacts
+                .iter()
+                .map(|(label, value)| format!("{}: {}", label, escape(value)))
+                .collect::<Vec<String>>()
+                .join("  \n");
+            md += "\n\n";
+        }
+
+        if !self.description.is_empty() {
+            md += &format!("## Summary\n\n{}\n\n", escape(&self.description));
+        }
+
+        for id in self.visible_sections() {
+            md += &self.markdown_section(&id);
+        }
+
+        md.trim_end().to_string() + "\n"
+    }
+
+    /// Render this resume as plain text, wrapping lines at `width` columns
+    pub fn to_plain_text(&self, width: usize) -> String {
+        let mut lines = vec![format!("{} {}", self.first_name, self.last_name).to_uppercase()];
+        if !self.profession.is_empty() {
+            lines.extend(wrap(&self.profession, width, ""));
+        }
+        for (label, value) in self.contact_lines() {
+            lines.extend(wrap(&format!("{}: {}", label, value), width, ""));
+        }
+
+        if !self.description.is_empty() {
+            lines.push(String::new());
+            lines.push(String::from("SUMMARY"));
+            lines.extend(wrap(&self.description, width, ""));
+        }
+
+        for id in self.visible_sections() {
+            let section = self.plain_text_section(&id, width);
+            if !section.is_empty() {
+                lines.push(String::new());
+                lines.push(id.title().to_uppercase());
+                lines.extend(section);
+            }
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    // The Markdown for a section under its heading, or nothing if it has no entries
+    fn markdown_section(&self, id: &SectionId) -> String {
+        let mut md = String::new();
+        match id {
+            SectionId::Skills => {
+                for (skill, level) in &self.skills {
+                    md += &match level {
+                        Some(level) => format!("- {} ({})\n", escape(skill), level),
+                        None => format!("- {}\n", escape(skill)),
+                    };
+                }
+                md += "\n";
+            }
+            SectionId::Education => {
+                for e in &self.education {
+                    md += &format!("### {}\n\n", escape(&e.school));
+                    if let Some(summary) = education_summary(e) {
+                        md += &format!("{}  \n", escape(&summary));
+                    }
+                    md += &format!("{}\n\n", Date::range(&e.start, &e.end));
+                }
+            }
+            SectionId::Experience => {
+                for j in &self.work_experience {
+                    md += &format!("### {}\n\n", escape(&work_heading(j)));
+                    md += &format!("{}\n\n", Date::range(&j.start, &j.end));
+                    if !j.description.is_empty() {
+                        md += &format!("{}\n\n", escape(&j.description));
+                    }
+                    md += &markdown_list(&j.highlights);
+                }
+            }
+            SectionId::Projects => {
+                for p in &self.projects {
+                    md += &match &p.url {
+                        Some(url) => {
+                            format!("### [{}](<{}>)\n\n", escape(&p.name), escape_url(url))
+                        }
+                        None => format!("### {}\n\n", escape(&p.name)),
+                    };
+                    md += &markdown_details(project_details(p));
+                    md += &markdown_list(&p.highlights);
+                }
+            }
+            SectionId::Custom(_) => {
+                for entry in self.custom_entries(id) {
+                    md += &format!("### {}\n\n", escape(&entry.heading));
+                    md += &markdown_details(custom_details(entry));
+                    md += &markdown_list(&entry.bullets);
+                }
+            }
+            _ => md += &markdown_list(&self.entry_lines(id)),
+        }
+
+        if md.trim().is_empty() {
+            String::new()
+        } else {
+            format!("## {}\n\n{}", escape(id.title()), md)
+        }
+    }
+
+    // The lines of a section without its heading, or none if it has no entries
+    fn plain_text_section(&self, id: &SectionId, width: usize) -> Vec<String> {
+        let mut lines = Vec::new();
+        match id {
+            SectionId::Skills => {
+                for (skill, level) in &self.skills {
+                    let skill = match level {
+                        Some(level) => format!("- {} ({})", skill, level),
+                        None => format!("- {}", skill),
+                    };
+                    lines.extend(wrap(&skill, width, "  "));
+                }
+            }
+            SectionId::Education => {
+                for e in &self.education {
+                    separate(&mut lines);
+                    lines.extend(wrap(&e.school, width, ""));
+                    if let Some(summary) = education_summary(e) {
+                        lines.extend(wrap(&summary, width, ""));
+                    }
+                    lines.push(Date::range(&e.start, &e.end));
+                }
+            }
+            SectionId::Experience => {
+                for j in &self.work_experience {
+                    separate(&mut lines);
+                    lines.extend(wrap(&work_heading(j), width, ""));
+                    lines.push(Date::range(&j.start, &j.end));
+                    if !j.description.is_empty() {
+                        lines.extend(wrap(&j.description, width, ""));
+                    }
+                    for highlight in &j.highlights {
+                        lines.extend(wrap(&format!("- {}", highlight), width, "  "));
+                    }
+                }
+            }
+            SectionId::Projects => {
+                for p in &self.projects {
+                    separate(&mut lines);
+                    lines.extend(wrap(&p.name, width, ""));
+                    if let Some(url) = &p.url {
+                        lines.extend(wrap(&format!("Website: {}", url), width, ""));
+                    }
+                    for detail in project_details(p) {
+                        lines.extend(wrap(&detail, width, ""));
+                    }
+                    for highlight in &p.highlights {
+                        lines.extend(wrap(&format!("- {}", highlight), width, "  "));
+                    }
+                }
+            }
+            SectionId::Custom(_) => {
+                for entry in self.custom_entries(id) {
+                    separate(&mut lines);
+                    lines.extend(wrap(&entry.heading, width, ""));
+                    for detail in custom_details(entry) {
+                        lines.extend(wrap(&detail, width, ""));
+                    }
+                    for bullet in &entry.bullets {
+                        lines.extend(wrap(&format!("- {}", bullet), width, "  "));
+                    }
+                }
+            }
+            _ => {
+                for entry in self.entry_lines(id) {
+                    lines.extend(wrap(&format!("- {}", entry), width, "  "));
+                }
+            }
+        }
+        lines
+    }
+
+    // The entries of a section written as one line each
+    fn entry_lines(&self, id: &SectionId) -> Vec<String> {
+        match id {
+            SectionId::Certifications => {
+                self.certifications.iter().map(certification_line).collect()
+            }
+            SectionId::Awards => self.awards.iter().map(award_line).collect(),
+            SectionId::Publications => self
+                .publications
+                .iter()
+                .map(|p| match p.link() {
+                    Some(link) => format!("{} {}", p.citation(), link),
+                    None => p.citation(),
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    // Labelled contact details, in the order they should be listed
+    fn contact_lines(&self) -> Vec<(&'static str, &str)> {
+        let contact = &self.contact_info;
+        vec![
+            ("Email", &contact.email),
+            ("Phone", &contact.phone),
+            ("Website", &contact.website),
+            ("GitHub", &contact.github),
+            ("LinkedIn", &contact.linkedin),
+        ]
+        .into_iter()
+        .filter_map(|(label, value)| value.as_deref().map(|value| (label, value)))
+        .collect()
+    }
+}
+
+fn education_summary(e: &Education) -> Option<String> {
+    match (&e.degree, &e.field) {
+        (Some(degree), Some(field)) => Some(format!("{} in {}", degree, field)),
+        (Some(degree), None) => Some(degree.to_string()),
+        (None, Some(field)) => Some(format!("Studied {}", field)),
+        (None, None) => None,
+    }
+}
+
+fn work_heading(j: &Work) -> String {
+    format!("{}, {}", j.position, j.company)
+}
+
+fn certification_line(c: &Certification) -> String {
+    let mut line = format!("{}, {}", c.name, c.issuer);
+    if let Some(validity) = c.validity() {
+        line += &format!(". {}", validity);
+    }
+    if let Some(id) = &c.credential_id {
+        line += &format!(". Credential ID {}", id);
+    }
+    if let Some(url) = &c.url {
+        line += &format!(". Verify at {}", url);
+    }
+    line
+}
+
+fn award_line(a: &Award) -> String {
+    let mut line = a.title.clone();
+    if let Some(issuer) = &a.issuer {
+        line += &format!(", {}", issuer);
+    }
+    if let Some(date) = a.date {
+        line += &format!(", {}", date);
+    }
+    if let Some(summary) = &a.summary {
+        line += &format!(". {}", summary);
+    }
+    line
+}
+
+// Everything about a project other than its name, website and highlights
+fn project_details(p: &Project) -> Vec<String> {
+    let mut details = Vec::new();
+    if let Some(role) = &p.role {
+        details.push(role.clone());
+    }
+    if let Some(dates) = p.dates() {
+        details.push(dates);
+    }
+    if let Some(repository) = &p.repository {
+        details.push(format!("Source: {}", repository));
+    }
+    if !p.technologies.is_empty() {
+        details.push(format!("Technologies: {}", p.technologies.join(", ")));
+    }
+    details
+}
+
+// Everything about an entry of a custom section other than its heading and bullets
+fn custom_details(entry: &CustomEntry) -> Vec<String> {
+    vec![
+        entry.subheading.clone(),
+        entry.location.clone(),
+        entry.dates(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+// Entries that take several lines are separated by a blank one
+fn separate(lines: &mut Vec<String>) {
+    if !lines.is_empty() {
+        lines.push(String::new());
+    }
+}
+
+// Details listed one per line, followed by a blank line
+fn markdown_details(details: Vec<String>) -> String {
+    if details.is_empty() {
+        String::new()
+    } else {
+        let details: Vec<String> = details.iter().map(|detail| escape(detail)).collect();
+        format!("{}\n\n", details.join("  \n"))
+    }
+}
+
+fn markdown_list(items: &[String]) -> String {
+    if items.is_empty() {
+        String::new()
+    } else {
+        items
+            .iter()
+            .map(|item| format!("- {}\n", escape(item)))
+            .collect::<String>()
+            + "\n"
+    }
+}
+
+/// Wrap `text` into lines of at most `width` characters, indenting every
+/// line after the first with `indent`. Words longer than a line are kept whole.
+fn wrap(text: &str, width: usize, indent: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let line_len = line.chars().count();
+            if line_len > 0 && line_len + 1 + word.chars().count() > width {
+                lines.push(line);
+                line = String::from(indent);
+            }
+            if !line.trim().is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Escape the characters that Markdown would treat as formatting, and the
+/// markers that would turn a line into a list item or heading underline
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            result.push('\n');
+        }
+        let content = line.trim_start();
+        result.push_str(&line[..line.len() - content.len()]);
+
+        // `- `, `+ ` and `=` at the start of a line, or the `.` or `)` after
+        // a number there
+        let digits = content.len()
+            - content
+                .trim_start_matches(|c: char| c.is_ascii_digit())
+                .len();
+        let marker = match content[digits..].chars().next() {
+            Some('-') | Some('+') | Some('=') if digits == 0 => Some(0),
+            Some('.') | Some(')') if digits > 0 => Some(digits),
+            _ => None,
+        };
+
+        for (j, ch) in content.char_indices() {
+            if "\\`*_[]<>#|".contains(ch) || Some(j) == marker {
+                result.push('\\');
+            }
+            result.push(ch);
+        }
+    }
+    result
+}
+
+/// Percent-encode the characters that would end a `<...>` link destination
+fn escape_url(url: &str) -> String {
+    url.replace('<', "%3C")
+        .replace('>', "%3E")
+        .replace('\n', "%0A")
+}
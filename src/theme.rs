@@ -1,4 +1,24 @@
-use super::{Color, HTML};
+use super::{
+    contrast::{contrast_ratio, ContrastIssue, AA_CONTRAST_RATIO},
+    Color,
+};
+
+pub(crate) const DOCUMENT_CSS: &str = r#"
+@import url('https://fonts.googleapis.com/css2?family=Merriweather:wght@300&display=swap');
+// @import url('https://fonts.googleapis.com/css2?family=Roboto&display=swap');
+@import url('https://fonts.googleapis.com/css2?family=Roboto:ital@1&display=swap');
+* {
+    // font-family: 'Merriweather', serif;
+    font-family: 'Roboto', sans-serif;
+}
+"#;
+
+// Falls back to fonts that are usually installed locally
+pub(crate) const OFFLINE_DOCUMENT_CSS: &str = r#"
+* {
+    font-family: Roboto, -apple-system, BlinkMacSystemFont, "Segoe UI", "Helvetica Neue", Arial, "Noto Sans", "Liberation Sans", sans-serif;
+}
+"#;
 
 pub trait Theme {
     fn get_color_rgb(&self, color: Color) -> (u8, u8, u8);
@@ -8,19 +28,102 @@ pub trait Theme {
     }
 
     fn get_document_css(&self) -> String {
-        r#"
-@import url('https://fonts.googleapis.com/css2?family=Merriweather:wght@300&display=swap');
-// @import url('https://fonts.googleapis.com/css2?family=Roboto&display=swap');
-@import url('https://fonts.googleapis.com/css2?family=Roboto:ital@1&display=swap');
-* {
-    // font-family: 'Merriweather', serif;
-    font-family: 'Roboto', sans-serif;
-}
-"#
-        .to_string()
+        DOCUMENT_CSS.to_string()
+    }
+
+    // Used instead of `get_document_css` for offline documents, so it must
+    // not import anything from the network. Themes whose section HTML uses
+    // classes other than Bootstrap's `card` should style them here too.
+    fn get_offline_document_css(&self) -> String {
+        OFFLINE_DOCUMENT_CSS.to_string()
     }
 
     fn compile_section_html(&self, content: String) -> String {
         format!("<div class=\"card\" style=\"height:100%; width:100%;\"><div class=\"card-body\" style=\"height:100%; width:100%;\">{}</div></div>", content)
     }
+
+    /// The WCAG contrast ratio of `color` against `DefaultBackground`
+    fn contrast_ratio(&self, color: Color) -> f64 {
+        contrast_ratio(
+            self.get_color_rgb(color),
+            self.get_color_rgb(Color::DefaultBackground),
+        )
+    }
+
+    /// The text colors of this theme that are too faint against its
+    /// background to meet WCAG AA. Wrap the theme in an `AccessibleTheme`
+    /// to fix them.
+    fn contrast_issues(&self) -> Vec<ContrastIssue> {
+        Color::text()
+            .iter()
+            .map(|color| ContrastIssue {
+                color: *color,
+                ratio: self.contrast_ratio(*color),
+            })
+            .filter(|issue| issue.ratio < AA_CONTRAST_RATIO)
+            .collect()
+    }
+}
+
+pub struct DefaultTheme;
+impl Theme for DefaultTheme {
+    fn get_color_rgb(&self, color: Color) -> (u8, u8, u8) {
+        match color {
+            Color::DefaultForeground | Color::Black => (0, 0, 0),
+            Color::DefaultBackground | Color::White => (255, 255, 255),
+            Color::Red => (255, 0, 0),
+            Color::Green => (0, 255, 0),
+            Color::DefaultSectionTitle | Color::Blue => (0, 0, 255),
+            Color::Yellow => (255, 255, 0),
+            Color::Orange => (255, 165, 0),
+            Color::Pink => (255, 192, 203),
+            Color::DefaultSubtitle => (255, 0, 255),
+            Color::Grey => (128, 128, 128),
+            Color::DefaultTitle | Color::Violet => (127, 0, 255),
+            Color::Brown => (165, 42, 42),
+        }
+    }
+}
+
+pub struct DraculaTheme;
+impl Theme for DraculaTheme {
+    fn get_color_rgb(&self, color: Color) -> (u8, u8, u8) {
+        match color {
+            Color::Black | Color::DefaultBackground => (40, 42, 54),
+            Color::White | Color::DefaultForeground => (248, 248, 242),
+            Color::Red => (255, 85, 85),
+            Color::Orange => (255, 184, 108),
+            Color::Yellow => (241, 250, 140),
+            Color::Green => (80, 250, 123),
+            Color::DefaultSectionTitle | Color::Blue => (139, 233, 253),
+            Color::DefaultTitle | Color::Violet => (189, 147, 249),
+
+            Color::Grey => (68, 71, 90),
+            Color::DefaultSubtitle | Color::Pink => (255, 121, 198),
+            Color::Brown => (165, 42, 42),
+        }
+    }
+}
+
+pub struct ForestTheme;
+impl Theme for ForestTheme {
+    fn get_color_rgb(&self, color: Color) -> (u8, u8, u8) {
+        match color {
+            Color::Black => (40, 42, 54),
+            Color::White | Color::DefaultForeground => (237, 245, 225), // #edf5e1
+            Color::Red => (255, 85, 85),
+            Color::Orange => (255, 184, 108),
+            Color::Yellow => (241, 250, 140),
+            Color::Green => (80, 250, 123),
+            Color::DefaultSectionTitle => (55, 150, 131), // #379683
+
+            Color::Blue | Color::DefaultBackground => (5, 56, 107), // #05386b
+            Color::DefaultTitle => (142, 228, 175),                 // #8ee4af
+            Color::Violet => (189, 147, 249),
+            Color::Grey => (68, 71, 90),
+            Color::DefaultSubtitle => (219, 92, 162), // #5cdb95
+            Color::Pink => (255, 121, 198),
+            Color::Brown => (165, 42, 42),
+        }
+    }
 }
@@ -0,0 +1,117 @@
+// The themes a resume can be compiled with. The built-in themes are always
+// registered first, and applications or other crates can add their own at
+// runtime with `register`, or swap one out with `replace`.
+
+use super::{
+    theme::{DefaultTheme, DraculaTheme, ForestTheme},
+    Theme,
+};
+use std::sync::{Arc, LazyLock, RwLock};
+
+// Makes a fresh instance of a registered theme
+type Constructor = Arc<dyn Fn() -> Box<dyn Theme> + Send + Sync>;
+
+// Every registered theme by name, in the order they were registered
+static REGISTRY: LazyLock<RwLock<Vec<(String, Constructor)>>> = LazyLock::new(|| {
+    let built_in: Vec<(String, Constructor)> = vec![
+        (String::from("Default"), Arc::new(|| Box::new(DefaultTheme))),
+        (String::from("Dracula"), Arc::new(|| Box::new(DraculaTheme))),
+        (String::from("Forest"), Arc::new(|| Box::new(ForestTheme))),
+    ];
+    RwLock::new(built_in)
+});
+
+// Read the registry under its lock, without copying it
+fn with_registry<T>(f: impl FnOnce(&[(String, Constructor)]) -> T) -> T {
+    f(&REGISTRY.read().unwrap())
+}
+
+fn position(registry: &[(String, Constructor)], name: &str) -> Option<usize> {
+    registry
+        .iter()
+        .position(|(existing, _)| existing.eq_ignore_ascii_case(name))
+}
+
+/// Add a theme under `name`, which `constructor` makes an instance of
+/// whenever the theme is used. Returns `false` and leaves the registry as
+/// it was if a theme is already registered under `name`, ignoring case.
+pub fn register(
+    name: impl Into<String>,
+    constructor: impl Fn() -> Box<dyn Theme> + Send + Sync + 'static,
+) -> bool {
+    let name = name.into();
+    let mut registry = REGISTRY.write().unwrap();
+    if position(&registry, &name).is_some() {
+        return false;
+    }
+    registry.push((name, Arc::new(constructor)));
+    true
+}
+
+/// Like `register`, but a theme registered under the name of an existing
+/// one, ignoring case, replaces it in the same place in the list.
+pub fn replace(
+    name: impl Into<String>,
+    constructor: impl Fn() -> Box<dyn Theme> + Send + Sync + 'static,
+) {
+    let name = name.into();
+    let constructor: Constructor = Arc::new(constructor);
+    let mut registry = REGISTRY.write().unwrap();
+    match position(&registry, &name) {
+        Some(i) => registry[i] = (name, constructor),
+        None => registry.push((name, constructor)),
+    }
+}
+
+/// The names of every registered theme, in the order they are listed
+pub fn names() -> Vec<String> {
+    with_registry(|registry| registry.iter().map(|(name, _)| name.clone()).collect())
+}
+
+/// Every registered theme, in the same order as `names`
+pub fn all() -> Vec<Box<dyn Theme>> {
+    // Constructors are called outside the lock, in case they look up a theme
+    let constructors: Vec<Constructor> = with_registry(|registry| {
+        registry
+            .iter()
+            .map(|(_, constructor)| constructor.clone())
+            .collect()
+    });
+    constructors
+        .iter()
+        .map(|constructor| constructor())
+        .collect()
+}
+
+/// The theme registered under `name`, ignoring case
+pub fn by_name(name: &str) -> Option<Box<dyn Theme>> {
+    ThemeOption::from_name(name).map(|option| option.get_theme())
+}
+
+/// A registered theme, by its place in the list of themes. Unlike a
+/// `Box<dyn Theme>` it can be copied and compared, so it can be used to
+/// remember which theme was picked, like in the wizard's radio buttons.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ThemeOption(usize);
+
+impl From<ThemeOption> for String {
+    fn from(option: ThemeOption) -> Self {
+        with_registry(|registry| registry[option.0].0.clone())
+    }
+}
+
+impl ThemeOption {
+    /// Every registered theme, in the order they are listed
+    pub fn all() -> Vec<Self> {
+        (0..with_registry(<[_]>::len)).map(Self).collect()
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        with_registry(|registry| position(registry, name)).map(Self)
+    }
+
+    pub fn get_theme(&self) -> Box<dyn Theme> {
+        let constructor = with_registry(|registry| registry[self.0].1.clone());
+        constructor()
+    }
+}
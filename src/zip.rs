@@ -0,0 +1,100 @@
+// Office documents are zip archives of XML files. This writes just enough
+// of the zip format for them: every entry is deflated, unless it is stored
+// as is, like the `mimetype` that starts an OpenDocument file.
+
+use flate2::{write::DeflateEncoder, Compression, Crc};
+use std::io::Write;
+
+// Every entry is dated 1980-01-01, the earliest date a zip file can hold,
+// so the same document always compiles to the same bytes
+const DOS_DATE: u16 = (1 << 5) | 1;
+// Zip 2.0, the first version with deflate
+const VERSION: u16 = 20;
+// Entry names are UTF-8
+const UTF8_NAMES: u16 = 1 << 11;
+
+#[derive(Default)]
+pub(crate) struct ZipWriter {
+    bytes: Vec<u8>,
+    // The central directory, which lists every entry at the end of the archive
+    directory: Vec<u8>,
+    entries: u16,
+}
+
+impl ZipWriter {
+    /// Add a compressed file
+    pub(crate) fn deflate(&mut self, name: &str, data: &[u8]) {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(data)
+            .and_then(|_| encoder.finish())
+            .map(|compressed| self.add(name, data, &compressed, 8))
+            .expect("writing to memory cannot fail")
+    }
+
+    /// Add a file without compressing it
+    pub(crate) fn store(&mut self, name: &str, data: &[u8]) {
+        self.add(name, data, data, 0)
+    }
+
+    fn add(&mut self, name: &str, data: &[u8], compressed: &[u8], method: u16) {
+        let mut crc = Crc::new();
+        crc.update(data);
+        let offset = self.bytes.len() as u32;
+
+        // The header before the entry's data and its copy in the central
+        // directory share most of their fields
+        let mut fields = Vec::new();
+        put16(&mut fields, VERSION);
+        put16(&mut fields, UTF8_NAMES);
+        put16(&mut fields, method);
+        put16(&mut fields, 0);
+        put16(&mut fields, DOS_DATE);
+        put32(&mut fields, crc.sum());
+        put32(&mut fields, compressed.len() as u32);
+        put32(&mut fields, data.len() as u32);
+        put16(&mut fields, name.len() as u16);
+        put16(&mut fields, 0);
+
+        put32(&mut self.bytes, 0x0403_4b50);
+        self.bytes.extend_from_slice(&fields);
+        self.bytes.extend_from_slice(name.as_bytes());
+        self.bytes.extend_from_slice(compressed);
+
+        put32(&mut self.directory, 0x0201_4b50);
+        put16(&mut self.directory, VERSION);
+        self.directory.extend_from_slice(&fields);
+        // No comment, on the first disk, with no file attributes
+        put16(&mut self.directory, 0);
+        put16(&mut self.directory, 0);
+        put16(&mut self.directory, 0);
+        put32(&mut self.directory, 0);
+        put32(&mut self.directory, offset);
+        self.directory.extend_from_slice(name.as_bytes());
+        self.entries += 1;
+    }
+
+    pub(crate) fn finish(mut self) -> Vec<u8> {
+        let offset = self.bytes.len() as u32;
+        let size = self.directory.len() as u32;
+        self.bytes.append(&mut self.directory);
+
+        put32(&mut self.bytes, 0x0605_4b50);
+        put16(&mut self.bytes, 0);
+        put16(&mut self.bytes, 0);
+        put16(&mut self.bytes, self.entries);
+        put16(&mut self.bytes, self.entries);
+        put32(&mut self.bytes, size);
+        put32(&mut self.bytes, offset);
+        put16(&mut self.bytes, 0);
+        self.bytes
+    }
+}
+
+fn put16(bytes: &mut Vec<u8>, value: u16) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put32(bytes: &mut Vec<u8>, value: u32) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
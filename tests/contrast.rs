@@ -0,0 +1,68 @@
+use resume::{
+    adjust_lightness, contrast_ratio, AccessibleTheme, Color, CustomTheme, Format, Theme,
+    AA_CONTRAST_RATIO,
+};
+
+const OCEAN: &str = include_str!("../themes/ocean.toml");
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 0.01,
+        "{} != {}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn known_ratios() {
+    assert_close(contrast_ratio((0, 0, 0), (255, 255, 255)), 21.0);
+    assert_close(contrast_ratio((255, 255, 255), (0, 0, 0)), 21.0);
+    assert_close(contrast_ratio((30, 144, 255), (30, 144, 255)), 1.0);
+    // #767676 is the lightest grey that passes AA on white
+    assert!(contrast_ratio((0x76, 0x76, 0x76), (255, 255, 255)) >= AA_CONTRAST_RATIO);
+    assert!(contrast_ratio((0x77, 0x77, 0x77), (255, 255, 255)) < AA_CONTRAST_RATIO);
+}
+
+#[test]
+fn adjusted_colors_keep_their_hue() {
+    // Orange is darkened on white, and blue lightened on black
+    let orange = adjust_lightness((255, 165, 0), (255, 255, 255), AA_CONTRAST_RATIO);
+    assert!(contrast_ratio(orange, (255, 255, 255)) >= AA_CONTRAST_RATIO);
+    let (r, g, b) = orange;
+    assert_eq!(b, 0);
+    assert_close(f64::from(g) / f64::from(r), 165.0 / 255.0);
+
+    let blue = adjust_lightness((0, 0, 139), (0, 0, 0), AA_CONTRAST_RATIO);
+    assert!(contrast_ratio(blue, (0, 0, 0)) >= AA_CONTRAST_RATIO);
+    let (r, g, b) = blue;
+    assert_eq!(r, g);
+    assert!(b > r);
+
+    // Colors that are already readable are left alone
+    assert_eq!(
+        adjust_lightness((0, 0, 0), (255, 255, 255), AA_CONTRAST_RATIO),
+        (0, 0, 0)
+    );
+}
+
+#[test]
+fn accessible_themes_fix_only_text() {
+    let document = OCEAN
+        .replace(
+            "default_foreground = \"#f0f8ff\"",
+            "default_foreground = \"#1a2a3a\"",
+        )
+        .replace("grey = \"#5c6370\"", "grey = \"#0c1e2f\"");
+    let theme = CustomTheme::from_document(&document, Format::Toml).unwrap();
+    let issues = theme.contrast_issues();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].color, Color::DefaultForeground);
+
+    let accessible = AccessibleTheme::new(&theme);
+    assert!(accessible.contrast_issues().is_empty());
+    assert_eq!(
+        accessible.get_color_rgb(Color::Grey),
+        theme.get_color_rgb(Color::Grey)
+    );
+}
@@ -0,0 +1,26 @@
+use resume::{CustomTheme, DocumentError, Format};
+
+const OCEAN: &str = include_str!("../themes/ocean.toml");
+
+#[test]
+fn markup_is_kept_out_of_styles() {
+    assert!(CustomTheme::from_document(OCEAN, Format::Toml).is_ok());
+
+    for (field, document) in [
+        (
+            "css",
+            OCEAN.replace("h4 {", "</style><script>alert(1)</script><style>h4 {"),
+        ),
+        (
+            "font_family",
+            OCEAN.replace("Georgia, serif", "Georgia</style><style>"),
+        ),
+    ]
+    .iter()
+    {
+        match CustomTheme::from_document(document, Format::Toml) {
+            Err(DocumentError::Parse { path, .. }) => assert_eq!(path, *field),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use resume::Date;
+
+#[test]
+fn months_out_of_range_do_not_panic() {
+    assert_eq!(Date::Month(2021, 0).to_string(), "Jan 2021");
+    assert_eq!(Date::Month(2021, 13).to_string(), "Dec 2021");
+    assert_eq!(
+        Date::duration(&Date::Month(2021, 0), &Date::Month(2021, 2)),
+        Some(String::from("2 mos"))
+    );
+}
+
+#[test]
+fn huge_years_do_not_overflow() {
+    assert_eq!(
+        Date::duration(&Date::Month(u32::MAX, 1), &Date::Month(u32::MAX, 2)),
+        None
+    );
+    assert_eq!(Date::duration(&Date::Year(0), &Date::Year(u32::MAX)), None);
+}
+
+#[test]
+fn years_are_bounded_when_read() {
+    assert!("999999999-01".parse::<Date>().is_err());
+    assert!("0".parse::<Date>().is_err());
+    assert!("10000".parse::<Date>().is_err());
+    assert_eq!("9999-12".parse(), Ok(Date::Month(9999, 12)));
+    assert_eq!("1".parse(), Ok(Date::Year(1)));
+}
+
+#[test]
+fn only_equal_dates_compare_equal() {
+    use std::cmp::Ordering;
+
+    let dates = [
+        Date::Year(2021),
+        Date::Month(2021, 0),
+        Date::Month(2021, 1),
+        Date::Year(u32::MAX),
+        Date::Month(u32::MAX, u32::MAX),
+        Date::Present,
+    ];
+    for (i, a) in dates.iter().enumerate() {
+        for (j, b) in dates.iter().enumerate() {
+            assert_eq!(a.cmp(b) == Ordering::Equal, a == b, "{:?} {:?}", a, b);
+            assert_eq!(a.cmp(b), i.cmp(&j), "{:?} {:?}", a, b);
+        }
+    }
+}
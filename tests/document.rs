@@ -0,0 +1,105 @@
+use resume::{Date, DocumentError, Format, LayoutEntry, Resume, SectionId};
+
+const RESUME: &str = include_str!("data/resume.toml");
+
+#[test]
+fn document_survives_a_round_trip() {
+    let resume = Resume::from_document(RESUME, Format::Toml).unwrap();
+    for format in [Format::Toml, Format::Json].iter() {
+        let document = resume.to_document(*format).unwrap();
+        assert_eq!(Resume::from_document(&document, *format).unwrap(), resume);
+    }
+}
+
+#[test]
+fn misspelled_fields_are_reported() {
+    let document = RESUME.replace("profession =", "profesion =");
+    match Resume::from_document(&document, Format::Toml) {
+        Err(DocumentError::Parse { path, .. }) => assert_eq!(path, "profesion"),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+
+    let document = Resume::from_document(RESUME, Format::Toml)
+        .unwrap()
+        .to_document(Format::Json)
+        .unwrap()
+        .replace("\"work_experience\"", "\"work_experiance\"");
+    match Resume::from_document(&document, Format::Json) {
+        Err(DocumentError::Parse { path, .. }) => assert_eq!(path, "work_experiance"),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
+
+#[test]
+fn newer_documents_are_rejected() {
+    let document = RESUME.replace("version = 3", "version = 99");
+    assert!(matches!(
+        Resume::from_document(&document, Format::Toml),
+        Err(DocumentError::UnsupportedVersion(99))
+    ));
+}
+
+#[test]
+fn older_documents_are_read() {
+    let document = r#"
+version = 1
+first_name = "Ada"
+last_name = "Lovelace"
+skills = [["Mathematics", "Expert"]]
+
+[[work_experience]]
+start_year = 1842
+end_year = 1843
+position = "Translator"
+company = "Scientific Memoirs"
+"#;
+    let resume = Resume::from_document(document, Format::Toml).unwrap();
+    assert_eq!(resume.work_experience[0].start, Date::Year(1842));
+    assert_eq!(resume.work_experience[0].end, Date::Year(1843));
+}
+
+#[test]
+fn layout_orders_and_hides_sections() {
+    let document = format!(
+        "{}\n{}",
+        RESUME,
+        r#"[layout]
+sections = [
+    { section = "custom:Volunteering" },
+    { section = "experience" },
+    { section = "skills", visible = false },
+    { section = "custom:Hobbies" },
+    { section = "experience", visible = false },
+]
+"#
+    );
+    let mut resume = Resume::from_document(&document, Format::Toml).unwrap();
+    let entry = |section, visible| LayoutEntry { section, visible };
+    let volunteering = || SectionId::Custom(String::from("Volunteering"));
+
+    // Unknown sections and repeated entries are dropped, and sections left
+    // out of the layout follow in their default order
+    assert_eq!(
+        resume.arrangement(),
+        vec![
+            entry(volunteering(), true),
+            entry(SectionId::Experience, true),
+            entry(SectionId::Skills, false),
+            entry(SectionId::Education, true),
+            entry(SectionId::Projects, true),
+            entry(SectionId::Certifications, true),
+            entry(SectionId::Awards, true),
+            entry(SectionId::Publications, true),
+        ]
+    );
+    assert!(!resume.visible_sections().contains(&SectionId::Skills));
+
+    // A renamed custom section loses its place and goes last
+    resume.custom_sections[0].title = String::from("Outreach");
+    let arrangement = resume.arrangement();
+    assert!(arrangement.iter().all(|e| e.section != volunteering()));
+    assert_eq!(
+        arrangement.last(),
+        Some(&entry(SectionId::Custom(String::from("Outreach")), true))
+    );
+}
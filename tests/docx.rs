@@ -0,0 +1,16 @@
+use resume::{ThemeOption, HTML};
+
+// Sections nested in cells narrower than their own padding
+fn narrow_sections() -> HTML {
+    HTML::row(
+        (0..100)
+            .map(|_| HTML::section(HTML::section(HTML::text("x"))))
+            .collect::<Vec<HTML>>(),
+    )
+}
+
+#[test]
+fn narrow_sections_are_written() {
+    let docx = narrow_sections().compile_docx(&*ThemeOption::default().get_theme());
+    assert!(docx.starts_with(b"PK"));
+}
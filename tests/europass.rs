@@ -0,0 +1,141 @@
+use resume::{Degree, Format, Proficiency, Resume};
+
+const RESUME: &str = include_str!("data/resume.toml");
+const EUROPASS: &str = include_str!("data/europass.xml");
+
+#[test]
+fn resume_survives_a_round_trip() {
+    let resume = Resume::from_document(RESUME, Format::Toml).unwrap();
+    let exported = resume.to_europass();
+    // Europass has room for a profession or a description, but not both
+    assert_eq!(exported.unmapped, vec![String::from("description")]);
+
+    let imported = Resume::from_europass(&exported.value).unwrap();
+    assert!(imported.unmapped.is_empty(), "{:?}", imported.unmapped);
+    assert_eq!(
+        imported.value,
+        Resume {
+            description: String::new(),
+            ..resume
+        }
+    );
+}
+
+#[test]
+fn description_is_kept_without_a_profession() {
+    let resume = Resume {
+        profession: String::new(),
+        ..Resume::from_document(RESUME, Format::Toml).unwrap()
+    };
+    let exported = resume.to_europass();
+    assert!(exported.unmapped.is_empty(), "{:?}", exported.unmapped);
+    assert_eq!(
+        Resume::from_europass(&exported.value).unwrap().value,
+        resume
+    );
+}
+
+#[test]
+fn europass_cv_survives_a_round_trip() {
+    let imported = Resume::from_europass(EUROPASS).unwrap();
+    assert_eq!(
+        imported.unmapped,
+        vec![
+            "LearnerInfo/Identification/Demographics",
+            "LearnerInfo/Identification/ContactInfo/Address",
+            "LearnerInfo/WorkExperienceList/WorkExperience[1]/Employer/ContactInfo",
+            "LearnerInfo/EducationList/Education[1]/Skills",
+            "LearnerInfo/EducationList/Education[1]/Title",
+            "LearnerInfo/Skills/Driving",
+            "LearnerInfo/Skills/Communication/Description",
+            "LearnerInfo/Skills/Computer/ProficiencyLevel",
+        ]
+    );
+
+    let exported = imported.value.to_europass();
+    assert!(exported.unmapped.is_empty(), "{:?}", exported.unmapped);
+    let reimported = Resume::from_europass(&exported.value).unwrap();
+    assert!(reimported.unmapped.is_empty(), "{:?}", reimported.unmapped);
+    assert_eq!(reimported.value, imported.value);
+}
+
+#[test]
+fn europass_cv_is_read() {
+    let resume = Resume::from_europass(EUROPASS).unwrap().value;
+    assert_eq!(resume.first_name, "Marie");
+    assert_eq!(resume.profession, "Data Engineer");
+    assert_eq!(resume.contact_info.linkedin.as_deref(), Some("mariedupont"));
+
+    let job = &resume.work_experience[0];
+    assert_eq!(
+        job.description,
+        "Builds the data platform of the logistics team."
+    );
+    assert_eq!(
+        job.highlights,
+        vec![
+            "Moved nightly batch jobs to streaming",
+            "Runs the R&D reading group"
+        ]
+    );
+
+    assert_eq!(resume.education[0].degree, Some(Degree::Masters));
+    // The title stands in for the missing field
+    assert_eq!(
+        resume.education[1].field.as_deref(),
+        Some("Licence de mathématiques")
+    );
+
+    assert_eq!(
+        resume.skills,
+        vec![
+            (String::from("Python"), Some(Proficiency::Expert)),
+            (String::from("Apache Kafka"), None),
+            (String::from("French"), Some(Proficiency::Expert)),
+            // Rated by the weakest of the five abilities
+            (String::from("English"), Some(Proficiency::Strong)),
+            (String::from("German"), Some(Proficiency::Barely)),
+        ]
+    );
+
+    assert_eq!(resume.certifications[0].issuer, "Google");
+    assert_eq!(resume.custom_sections[0].title, "Conferences");
+    assert_eq!(
+        resume.custom_sections[0].entries[0].bullets,
+        vec!["Speaker on stream processing"]
+    );
+}
+
+#[test]
+fn degrees_map_to_eqf_levels() {
+    let levels: Vec<u8> = Degree::all()
+        .iter()
+        .map(|degree| degree.eqf_level())
+        .collect();
+    assert_eq!(levels, vec![4, 5, 6, 7, 8]);
+    for degree in Degree::all().iter() {
+        assert_eq!(Degree::from_eqf_level(degree.eqf_level()), Some(*degree));
+    }
+    assert_eq!(Degree::from_eqf_level(3), None);
+}
+
+#[test]
+fn proficiency_maps_to_cefr_levels() {
+    for level in Proficiency::all().iter() {
+        match level.cefr_level() {
+            Some(cefr) => assert_eq!(Proficiency::from_cefr_level(cefr), Some(*level)),
+            None => assert_eq!(*level, Proficiency::None),
+        }
+    }
+    assert_eq!(
+        Proficiency::from_cefr_level("c1"),
+        Some(Proficiency::Expert)
+    );
+    assert_eq!(Proficiency::from_cefr_level("D1"), None);
+}
+
+#[test]
+fn other_documents_are_rejected() {
+    assert!(Resume::from_europass("<html></html>").is_err());
+    assert!(Resume::from_europass("not xml").is_err());
+}
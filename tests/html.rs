@@ -0,0 +1,245 @@
+use resume::{
+    ClassicTemplate, Color, CompactTemplate, ContactInfo, Date, Format, Resume, SidebarTemplate,
+    ThemeOption, TimelineTemplate, HTML,
+};
+
+const RESUME: &str = include_str!("data/resume.toml");
+
+#[test]
+fn skill_bars_use_the_theme() {
+    let theme = ThemeOption::default().get_theme();
+    let fill = format!(
+        ".progress-bar {{ background-color: {}; }}",
+        theme.get_color_hex(Color::DefaultSectionTitle)
+    );
+    let html = Resume::from_document(RESUME, Format::Toml)
+        .unwrap()
+        .generate();
+    for page in [html.compile(&*theme), html.compile_offline(&*theme)].iter() {
+        assert!(page.contains(&fill), "{}", page);
+        assert!(!page.contains("background-color: #007bff"), "{}", page);
+    }
+}
+
+#[test]
+fn user_text_is_escaped() {
+    let mut resume = Resume::from_document(RESUME, Format::Toml).unwrap();
+    resume.description = String::from("<script>alert(1)</script>");
+    resume.projects[0].url = Some(String::from("https://example.com/?q=\"><b>"));
+    resume.projects[0].repository = Some(String::from(" JavaScript:alert(1)"));
+    let page = resume
+        .generate()
+        .compile(&*ThemeOption::default().get_theme());
+
+    assert!(!page.contains("<script>alert"), "{}", page);
+    assert!(page.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+    assert!(page.contains("AT&amp;T University"));
+    assert!(!page.contains("AT&T"));
+    assert!(page.contains("href=\"https://example.com/?q=&quot;&gt;&lt;b&gt;\""));
+    assert!(!page.to_lowercase().contains("javascript:"), "{}", page);
+    assert!(page.contains("<a href=\"#\">"));
+}
+
+#[test]
+fn expired_certifications_are_flagged() {
+    let theme = ThemeOption::default().get_theme();
+    let red = format!(
+        "<div style=\"color: {};\">",
+        theme.get_color_hex(Color::Red)
+    );
+    let mut resume = Resume::from_document(RESUME, Format::Toml).unwrap();
+
+    resume.certifications[0].expires = Some(Date::Month(2001, 3));
+    assert!(resume.certifications[0].is_expired());
+    let page = resume.generate().compile(&*theme);
+    assert!(page.contains(&format!(
+        "{}<p><i>Issued Mar 2021, expired Mar 2001</i></p>",
+        red
+    )));
+
+    resume.certifications[0].expires = Some(Date::Month(9999, 3));
+    assert!(!resume.certifications[0].is_expired());
+    let page = resume.generate().compile(&*theme);
+    assert!(page.contains("Issued Mar 2021, expires Mar 9999"));
+    assert!(!page.contains(&red));
+}
+
+// The blocks of a page, inside the document, container and column every
+// template wraps them in
+fn blocks(page: &HTML) -> &[HTML] {
+    match page {
+        HTML::Document(items) => match items.as_slice() {
+            [HTML::Container(items)] => match items.as_slice() {
+                [HTML::Column(blocks)] => blocks,
+                other => panic!("expected a column, got {:?}", other),
+            },
+            other => panic!("expected a container, got {:?}", other),
+        },
+        other => panic!("expected a document, got {:?}", other),
+    }
+}
+
+// The headings of the sections inside `html`, in order
+fn headings(html: &HTML) -> Vec<String> {
+    let mut found = Vec::new();
+    if let HTML::SectionTitle(title) = html {
+        // The profession is shown in the header like a heading
+        if title != "Software Engineer" {
+            found.push(title.clone());
+        }
+    }
+    for child in html.children() {
+        found.extend(headings(child));
+    }
+    found
+}
+
+#[test]
+fn templates_arrange_sections() {
+    let resume = Resume::from_document(RESUME, Format::Toml).unwrap();
+    let all = [
+        "Skills",
+        "Education",
+        "Professional Experience",
+        "Projects",
+        "Certifications",
+        "Awards",
+        "Publications",
+        "Volunteering",
+    ];
+
+    // Classic: skills beside the header, then one section after another
+    let page = resume.generate_with(&ClassicTemplate);
+    match &blocks(&page)[0] {
+        HTML::Row(top) => {
+            assert_eq!(top.len(), 2);
+            assert_eq!(headings(&top[1]), ["Skills"]);
+        }
+        other => panic!("expected the header and skills in a row, got {:?}", other),
+    }
+    assert_eq!(blocks(&page).len(), all.len());
+    assert!(blocks(&page)[1..]
+        .iter()
+        .all(|block| matches!(block, HTML::Section(_))));
+    assert_eq!(headings(&page), all);
+
+    // Sidebar: a narrow column of short sections beside a wide one
+    let page = resume.generate_with(&SidebarTemplate);
+    match blocks(&page) {
+        [HTML::Grid(columns)] => {
+            assert_eq!(columns[0].0, 4);
+            assert_eq!(columns[1].0, 8);
+            assert_eq!(
+                headings(&columns[0].1),
+                ["Skills", "Certifications", "Awards"]
+            );
+            assert_eq!(
+                headings(&columns[1].1),
+                [
+                    "Education",
+                    "Professional Experience",
+                    "Projects",
+                    "Publications",
+                    "Volunteering"
+                ]
+            );
+        }
+        other => panic!("expected a grid, got {:?}", other),
+    }
+
+    // Compact: a single card, with skills on one line instead of bars
+    let page = resume.generate_with(&CompactTemplate);
+    match blocks(&page) {
+        [HTML::Section(_)] => {}
+        other => panic!("expected a single section, got {:?}", other),
+    }
+    assert!(!page.contains(&|html| matches!(html, HTML::PercentBar(..))));
+    assert!(page.contains(&|html| matches!(html, HTML::Span(text) if text == "Skills: ")));
+
+    // Timeline: dates in a narrow column beside each dated entry
+    let page = resume.generate_with(&TimelineTemplate);
+    assert_eq!(headings(&page), all);
+    assert!(page.contains(&|html| match html {
+        HTML::Grid(columns) => columns.len() == 2 && columns[0].0 == 3 && columns[1].0 == 9,
+        _ => false,
+    }));
+}
+
+#[test]
+fn contact_details_are_checked() {
+    let valid = ContactInfo {
+        email: Some(String::from("ada@example.com")),
+        phone: Some(String::from("+44 (20) 7946-0000")),
+        website: Some(String::from("ada.example.com")),
+        github: Some(String::from("https://github.com/ada-l")),
+        linkedin: Some(String::from("@adalovelace")),
+    };
+    assert!(valid.problems().is_empty(), "{:?}", valid.problems());
+
+    let invalid = ContactInfo {
+        email: Some(String::from("ada@example")),
+        phone: Some(String::from("12-34")),
+        website: Some(String::from("ftp://ada.example.com")),
+        github: Some(String::from("ada--l")),
+        linkedin: Some(String::from("linkedin.com/in/a")),
+    };
+    assert_eq!(
+        invalid.problems(),
+        [
+            "`ada@example` is not an email address",
+            "`12-34` is not a phone number",
+            "`ftp://ada.example.com` is not a website address",
+            "`ada--l` is not a GitHub username",
+            "`linkedin.com/in/a` is not a LinkedIn profile",
+        ]
+    );
+}
+
+#[test]
+fn contact_details_are_linked() {
+    let contact = ContactInfo {
+        email: Some(String::from("ada@example.com")),
+        phone: Some(String::from("+44 20 7946 0000")),
+        website: Some(String::from("ada.example.com")),
+        github: Some(String::from("ada")),
+        linkedin: Some(String::from("https://www.linkedin.com/in/adalovelace/")),
+    };
+    let links: Vec<(&str, String, String)> = [
+        ("Email", "ada@example.com", "mailto:ada@example.com"),
+        ("Phone", "+44 20 7946 0000", "tel:+442079460000"),
+        ("Website", "ada.example.com", "https://ada.example.com"),
+        ("GitHub", "github.com/ada", "https://github.com/ada"),
+        (
+            "LinkedIn",
+            "www.linkedin.com/in/adalovelace",
+            "https://www.linkedin.com/in/adalovelace/",
+        ),
+    ]
+    .iter()
+    .map(|&(label, text, url)| (label, String::from(text), String::from(url)))
+    .collect();
+    assert_eq!(contact.links(), links);
+}
+
+#[test]
+fn offline_pages_inline_their_styles() {
+    let resume = Resume::from_document(RESUME, Format::Toml).unwrap();
+    for theme in resume::themes::all() {
+        let page = resume.generate().compile_offline(&*theme);
+        let head = &page[..page.find("<body>").unwrap()];
+        assert!(!head.contains("<link"), "{}", head);
+        assert!(!head.contains("<script"), "{}", head);
+        assert!(!head.contains("bootstrapcdn"), "{}", head);
+        assert!(!head.contains("fonts.googleapis.com"), "{}", head);
+        // Only the styles the page uses are included
+        assert!(head.contains(".card {"));
+        assert!(head.contains(".progress {"));
+        assert!(!head.contains(".col-3"));
+    }
+
+    // Online pages link Bootstrap instead
+    let page = resume
+        .generate()
+        .compile(&*ThemeOption::default().get_theme());
+    assert!(page.contains("<link rel=\"stylesheet\" href=\"https://stackpath.bootstrapcdn.com/"));
+}
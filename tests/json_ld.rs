@@ -0,0 +1,61 @@
+use resume::{Format, Resume, ThemeOption};
+use serde_json::Value;
+
+const RESUME: &str = include_str!("data/resume.toml");
+const SCRIPT: &str = "<script type=\"application/ld+json\">";
+
+// The JSON-LD block of a page, up to where the browser would end it
+fn json_ld(page: &str) -> &str {
+    let start = page.find(SCRIPT).expect("no JSON-LD block") + SCRIPT.len();
+    let end = page[start..].find("</script>").unwrap();
+    &page[start..start + end]
+}
+
+#[test]
+fn person_is_described() {
+    let resume = Resume::from_document(RESUME, Format::Toml).unwrap();
+    let person: Value = serde_json::from_str(&resume.to_json_ld()).unwrap();
+    assert_eq!(person["@type"], "Person");
+    assert_eq!(person["name"], "Ada Lovelace");
+    assert_eq!(person["jobTitle"], "Software Engineer");
+    assert_eq!(person["alumniOf"][0]["name"], "AT&T University");
+    assert_eq!(person["email"], "ada@example.com");
+    assert_eq!(
+        person["sameAs"],
+        serde_json::json!([
+            "https://github.com/ada",
+            "https://www.linkedin.com/in/adalovelace"
+        ])
+    );
+}
+
+#[test]
+fn markup_cannot_escape_the_script() {
+    let resume = Resume {
+        description: String::from("<!--<script></script>-->"),
+        ..Resume::from_document(RESUME, Format::Toml).unwrap()
+    };
+    let page = resume
+        .generate()
+        .with_json_ld(resume.to_json_ld())
+        .compile(&*ThemeOption::default().get_theme());
+
+    let data = json_ld(&page);
+    assert!(!data.contains(['<', '>', '&'].as_ref()), "{}", data);
+    let person: Value = serde_json::from_str(data).unwrap();
+    assert_eq!(person["description"], resume.description);
+    assert_eq!(person["alumniOf"][0]["name"], "AT&T University");
+}
+
+#[test]
+fn description_is_a_note_only_when_given() {
+    let theme = ThemeOption::default().get_theme();
+    let resume = Resume::from_document(RESUME, Format::Toml).unwrap();
+    assert!(resume.generate().compile(&*theme).contains("p-note"));
+
+    let resume = Resume {
+        description: String::new(),
+        ..resume
+    };
+    assert!(!resume.generate().compile(&*theme).contains("p-note"));
+}
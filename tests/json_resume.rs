@@ -0,0 +1,157 @@
+use resume::{Date, Degree, Format, Proficiency, Resume};
+
+const RESUME: &str = include_str!("data/resume.toml");
+const JSON_RESUME: &str = include_str!("data/json_resume.json");
+
+#[test]
+fn resume_survives_a_round_trip() {
+    let resume = Resume::from_document(RESUME, Format::Toml).unwrap();
+    let exported = resume.to_json_resume().unwrap();
+    assert_eq!(
+        exported.unmapped,
+        vec![
+            "certifications[0].credential_id",
+            "certifications[0].expires",
+            "projects[0].repository",
+            "publications[0].authors",
+            "custom_sections[0]",
+        ]
+    );
+
+    let imported = Resume::from_json_resume(&exported.value).unwrap();
+    assert!(imported.unmapped.is_empty(), "{:?}", imported.unmapped);
+
+    // Only what the schema has no field for is lost
+    let mut expected = resume;
+    expected.certifications[0].credential_id = None;
+    expected.certifications[0].expires = None;
+    expected.projects[0].repository = None;
+    // The schema leaves out the end of anything still going on
+    expected.projects[0].end = None;
+    expected.publications[0].authors.clear();
+    expected.custom_sections.clear();
+    expected.layout = imported.value.layout.clone();
+    assert_eq!(imported.value, expected);
+}
+
+#[test]
+fn json_resume_survives_a_round_trip() {
+    let imported = Resume::from_json_resume(JSON_RESUME).unwrap().value;
+    let exported = imported.to_json_resume().unwrap();
+    assert!(exported.unmapped.is_empty(), "{:?}", exported.unmapped);
+    let reimported = Resume::from_json_resume(&exported.value).unwrap();
+    assert!(reimported.unmapped.is_empty(), "{:?}", reimported.unmapped);
+    // Profile links are kept as usernames, so compare what is written out
+    assert_eq!(
+        reimported.value.to_json_resume().unwrap().value,
+        exported.value
+    );
+}
+
+#[test]
+fn unmapped_fields_are_reported() {
+    assert_eq!(
+        Resume::from_json_resume(JSON_RESUME).unwrap().unmapped,
+        vec![
+            "volunteer",
+            "basics.location",
+            // Only GitHub and LinkedIn profiles have a place
+            "basics.profiles[1]",
+            "work[0].location",
+            // A job without a start date can't be shown
+            "work[2]",
+            "education[2].score",
+            "education[3].studyType",
+            "skills[2].level",
+        ]
+    );
+}
+
+#[test]
+fn json_resume_is_read() {
+    let resume = Resume::from_json_resume(JSON_RESUME).unwrap().value;
+    assert_eq!(resume.first_name, "Grace");
+    assert_eq!(resume.last_name, "Brewster Hopper");
+    assert_eq!(resume.contact_info.github.as_deref(), Some("grace"));
+    assert_eq!(
+        resume.contact_info.linkedin.as_deref(),
+        Some("https://www.linkedin.com/in/gracehopper")
+    );
+
+    // Days are dropped, since resumes only show months
+    let job = &resume.work_experience[0];
+    assert_eq!(
+        (job.start, job.end),
+        (Date::Month(1949, 3), Date::Month(1952, 11))
+    );
+    // A job without an end date is still going on
+    assert_eq!(resume.work_experience[1].end, Date::Present);
+    assert_eq!(resume.education[0].end, Date::Month(1934, 6));
+}
+
+#[test]
+fn study_types_map_to_degrees() {
+    let resume = Resume::from_json_resume(JSON_RESUME).unwrap().value;
+    let degrees: Vec<Option<Degree>> = resume.education.iter().map(|e| e.degree).collect();
+    assert_eq!(
+        degrees,
+        vec![
+            Some(Degree::PhD),
+            Some(Degree::Masters),
+            Some(Degree::Bachelors),
+            None
+        ]
+    );
+
+    for (study_type, degree) in [
+        ("High School Diploma", Degree::HighSchoolDiploma),
+        ("A.A.S.", Degree::Associates),
+        ("Bachelor of Science", Degree::Bachelors),
+        ("BSc", Degree::Bachelors),
+        ("Master of Arts", Degree::Masters),
+        ("MBA", Degree::Masters),
+        ("Doctor of Philosophy", Degree::PhD),
+    ]
+    .iter()
+    {
+        let json = format!(
+            r#"{{"education": [{{"institution": "X", "studyType": "{}", "startDate": "2000"}}]}}"#,
+            study_type
+        );
+        let imported = Resume::from_json_resume(&json).unwrap().value;
+        assert_eq!(
+            imported.education[0].degree,
+            Some(*degree),
+            "{}",
+            study_type
+        );
+    }
+}
+
+#[test]
+fn skill_levels_are_mapped() {
+    let resume = Resume::from_json_resume(JSON_RESUME).unwrap().value;
+    assert_eq!(
+        resume.skills,
+        vec![
+            (String::from("COBOL"), Some(Proficiency::Expert)),
+            (String::from("FORTRAN"), Some(Proficiency::Some)),
+            // Levels that are not understood are left out
+            (String::from("Assembly"), None),
+            (String::from("Nanoseconds"), None),
+        ]
+    );
+
+    // Every level is written in a way that is read back the same
+    for level in Proficiency::all().iter() {
+        let exported = Resume {
+            skills: vec![(String::from("Rust"), Some(*level))],
+            ..Resume::from_document(RESUME, Format::Toml).unwrap()
+        }
+        .to_json_resume()
+        .unwrap()
+        .value;
+        let imported = Resume::from_json_resume(&exported).unwrap().value;
+        assert_eq!(imported.skills[0].1, Some(*level));
+    }
+}
@@ -0,0 +1,48 @@
+use resume::{ContactInfo, Format, Resume, ThemeOption};
+
+const RESUME: &str = include_str!("data/resume.toml");
+
+fn latex_contact(contact_info: ContactInfo) -> String {
+    let resume = Resume {
+        contact_info,
+        ..Resume::from_document(RESUME, Format::Toml).unwrap()
+    };
+    resume.to_latex(&*ThemeOption::default().get_theme())
+}
+
+#[test]
+fn contact_links_are_built_by_moderncv() {
+    // moderncv adds the scheme and profile address itself
+    let tex = latex_contact(ContactInfo {
+        website: Some(String::from("https://ada.example.com/engine")),
+        github: Some(String::from("https://github.com/ada")),
+        linkedin: Some(String::from("www.linkedin.com/in/ada-lovelace/")),
+        ..ContactInfo::default()
+    });
+    assert!(
+        tex.contains("\\homepage{ada.example.com/engine}\n"),
+        "{}",
+        tex
+    );
+    assert!(tex.contains("\\social[github]{ada}\n"), "{}", tex);
+    assert!(
+        tex.contains("\\social[linkedin]{ada-lovelace}\n"),
+        "{}",
+        tex
+    );
+}
+
+#[test]
+fn contact_text_is_escaped() {
+    let tex = latex_contact(ContactInfo {
+        website: Some(String::from("ada.example.com/my_page#top")),
+        linkedin: Some(String::from("ada_l")),
+        ..ContactInfo::default()
+    });
+    assert!(
+        tex.contains("\\homepage{ada.example.com/my\\_page\\#top}\n"),
+        "{}",
+        tex
+    );
+    assert!(tex.contains("\\social[linkedin]{ada\\_l}\n"), "{}", tex);
+}
@@ -0,0 +1,29 @@
+use resume::{Format, Resume};
+
+const RESUME: &str = include_str!("data/resume.toml");
+
+#[test]
+fn lines_cannot_start_blocks() {
+    let resume = Resume {
+        description: String::from(
+            "- not a list\n2021. A good year\n  + nor this\n===\n# nor a heading",
+        ),
+        ..Resume::from_document(RESUME, Format::Toml).unwrap()
+    };
+    let md = resume.to_markdown();
+    assert!(md.contains(
+        "## Summary\n\n\\- not a list\n2021\\. A good year\n  \\+ nor this\n\\===\n\\# nor a heading\n\n"
+    ), "{}", md);
+}
+
+#[test]
+fn link_destinations_stay_closed() {
+    let mut resume = Resume::from_document(RESUME, Format::Toml).unwrap();
+    resume.projects[0].url = Some(String::from("https://example.com/<engine>"));
+    let md = resume.to_markdown();
+    assert!(
+        md.contains("### [Difference Engine](<https://example.com/%3Cengine%3E>)"),
+        "{}",
+        md
+    );
+}
@@ -0,0 +1,13 @@
+use resume::{ThemeOption, HTML};
+
+#[test]
+fn narrow_sections_are_written() {
+    // Sections nested in cells narrower than their own padding
+    let html = HTML::row(
+        (0..100)
+            .map(|_| HTML::section(HTML::section(HTML::text("x"))))
+            .collect::<Vec<HTML>>(),
+    );
+    let odt = html.compile_odt(&*ThemeOption::default().get_theme());
+    assert!(odt.starts_with(b"PK"));
+}
@@ -0,0 +1,31 @@
+use resume::{theme::ForestTheme, themes, Theme, ThemeOption};
+
+#[test]
+fn registered_names_are_not_taken_over() {
+    assert!(!themes::register("dracula", || Box::new(ForestTheme)));
+    let dracula = ThemeOption::from_name("Dracula").unwrap();
+    assert_eq!(String::from(dracula), "Dracula");
+
+    assert!(themes::register("Moss", || Box::new(ForestTheme)));
+    assert!(!themes::register("MOSS", || Box::new(ForestTheme)));
+    assert_eq!(
+        themes::names()
+            .iter()
+            .filter(|name| *name == "Moss")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn themes_can_be_replaced_on_purpose() {
+    let forest = ThemeOption::from_name("Forest").unwrap();
+    themes::replace("FOREST", || Box::new(ForestTheme));
+    // The replacement keeps the place of the theme it replaced
+    assert_eq!(ThemeOption::from_name("Forest"), Some(forest));
+    assert_eq!(String::from(forest), "FOREST");
+    assert_eq!(
+        forest.get_theme().get_document_css(),
+        ForestTheme.get_document_css()
+    );
+}